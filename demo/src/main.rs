@@ -258,7 +258,7 @@ pub async fn main() -> Result<(), Error> {
             pulse_2.push(x as f64);
             sleep(Duration::from_millis(500 - x as u64 * 10)).await;
         }
-        alert.notify("Both loops ended!");
+        alert.warn("Both loops ended!");
         sleep(Duration::from_secs(1)).await;
     }
     //rillrate::uninstall()?;
@@ -1,34 +1,57 @@
 use crate::config::cases::Layout;
+use crate::config::format::Format;
+use crate::config::validation::{self, Problem};
 use anyhow::Error;
 use async_trait::async_trait;
 use meio::task::{HeartBeat, OnTick, Tick};
-use meio::{Action, ActionHandler, Actor, Context, InterruptedBy, StartedBy, TaskAddress};
+use meio::{
+    Action, ActionHandler, Actor, Consumer, Context, InterruptedBy, StartedBy, TaskAddress,
+};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use rate_core::assets::Assets;
 use rill_protocol::diff::diff_full;
 use rill_protocol::io::provider::Path;
+use rrpack_basis::manifest::issues::global::ISSUES;
+use rrpack_basis::manifest::issues::LayoutIssue;
 use rrpack_basis::manifest::layouts::global::LAYOUTS;
+use rrpack_basis::manifest::paths::global::PATHS;
 use std::collections::HashMap;
 use std::path::Path as FilePath;
 use std::time::Duration;
 use strum::{EnumIter, IntoEnumIterator};
 use tokio::fs;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
-const CASE_EXT: &str = "xml";
 const PATH: &str = ".rillrate";
 
 pub struct ConfigWatcher {
     watcher: Option<RecommendedWatcher>,
     layouts: HashMap<Path, Layout>,
+    /// Loaded files of embedded layouts.
+    embedded: HashMap<String, LayoutFile>,
+    /// Loaded files of the config directory.
+    files: HashMap<String, LayoutFile>,
+    /// Published issues of files.
+    issues: HashMap<String, Vec<LayoutIssue>>,
     heartbeat: Option<TaskAddress<HeartBeat>>,
 }
 
+/// A parsed layout file.
+struct LayoutFile {
+    layout: Option<Layout>,
+    /// Issues found on loading. Flows are checked on publishing.
+    issues: Vec<LayoutIssue>,
+}
+
 impl ConfigWatcher {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             watcher: None,
             layouts: HashMap::new(),
+            embedded: HashMap::new(),
+            files: HashMap::new(),
+            issues: HashMap::new(),
             heartbeat: None,
         }
     }
@@ -36,6 +59,7 @@ impl ConfigWatcher {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum Group {
+    Paths,
     HeartBeat,
 }
 
@@ -51,6 +75,9 @@ impl Action for Reload {}
 impl<T: Actor> StartedBy<T> for ConfigWatcher {
     async fn handle(&mut self, ctx: &mut Context<Self>) -> Result<(), Error> {
         ctx.termination_sequence(Group::iter().collect());
+        // Flows are registered later, that's why they are checked on changes of paths
+        let rx = UnboundedReceiverStream::new(PATHS.subscribe());
+        ctx.attach(rx, (), Group::Paths);
         self.read_and_watch(ctx).await;
         Ok(())
     }
@@ -80,6 +107,7 @@ impl ConfigWatcher {
         } else {
             self.stop_heartbeat();
         }
+        self.publish_issues();
     }
 
     fn start_heartbeat(&mut self, ctx: &mut Context<Self>) {
@@ -113,7 +141,7 @@ impl ConfigWatcher {
                     let changed = event
                         .paths
                         .iter()
-                        .any(|p| p.as_path().extension() == Some(CASE_EXT.as_ref()));
+                        .any(|p| Format::from_path(p.as_path()).is_some());
                     if changed {
                         if let Err(err) = addr.blocking_act(Reload) {
                             log::error!(
@@ -141,23 +169,24 @@ impl ConfigWatcher {
         // TODO: Skip that step using config
         if let Some(data) = crate::preserved::PRESERVED.get() {
             let assets = Assets::parse(data)?;
+            let mut embedded = HashMap::new();
             for (path, data) in assets.iter() {
-                if path.contains("cases") {
-                    let layout: Result<Layout, _> = serde_xml_rs::from_reader(data);
-                    match layout {
-                        Ok(layout) => {
-                            let path = layout.name.clone();
-                            log::debug!("Add Embedded Layout: {}", path);
-                            // Embedded layouts aren't tracked by the `self.layouts` map
-                            // and they exists always.
-                            LAYOUTS.add_tab(path, layout.into());
-                        }
-                        Err(err) => {
-                            log::error!("Can't parse embedded layout file {}: {}", path, err);
-                        }
+                let format = Format::from_path(path.as_ref()).filter(|_| path.contains("cases"));
+                if let Some(format) = format {
+                    let data = String::from_utf8_lossy(data).into_owned();
+                    let file = format!("embedded:{}", path);
+                    let loaded = load_layout(&file, format, &data);
+                    if let Some(layout) = loaded.layout.clone() {
+                        let path = layout.name.clone();
+                        log::debug!("Add Embedded Layout: {}", path);
+                        // Embedded layouts aren't tracked by the `self.layouts` map
+                        // and they exists always.
+                        LAYOUTS.add_tab(path, layout.into());
                     }
+                    embedded.insert(file, loaded);
                 }
             }
+            self.embedded = embedded;
         }
         Ok(())
     }
@@ -166,22 +195,23 @@ impl ConfigWatcher {
         log::info!("Reading confing files");
         let mut dir = fs::read_dir(".rillrate/cases").await?;
         let mut layouts = HashMap::new();
+        let mut files = HashMap::new();
         while let Some(entry) = dir.next_entry().await? {
             let meta = entry.metadata().await?;
-            if meta.is_file() && entry.path().extension() == Some(CASE_EXT.as_ref()) {
-                let path = entry.path();
+            let path = entry.path();
+            let format = Format::from_path(path.as_path()).filter(|_| meta.is_file());
+            if let Some(format) = format {
                 let data = fs::read_to_string(path.as_path()).await?;
-                let layout: Result<Layout, _> = serde_xml_rs::from_str(&data);
-                match layout {
-                    Ok(layout) => {
-                        layouts.insert(layout.name.clone(), layout);
-                    }
-                    Err(err) => {
-                        log::error!("Can't parse layout file {}: {}", path.display(), err);
-                    }
+                let file = path.display().to_string();
+                let loaded = load_layout(&file, format, &data);
+                if let Some(layout) = loaded.layout.clone() {
+                    layouts.insert(layout.name.clone(), layout);
                 }
+                files.insert(file, loaded);
             }
         }
+        self.files = files;
+        self.publish_issues();
         let (to_add, to_remove, to_check) = diff_full(self.layouts.keys(), layouts.keys());
         for name in to_add {
             let layout = layouts.get(&name).unwrap();
@@ -207,6 +237,80 @@ impl ConfigWatcher {
     }
 }
 
+impl ConfigWatcher {
+    /// Checks flows of layouts, sends changed issues to the dashboard
+    /// and clears issues of fixed files.
+    fn publish_issues(&mut self) {
+        let mut issues = HashMap::new();
+        for (file, loaded) in self.embedded.iter().chain(&self.files) {
+            let mut file_issues = loaded.issues.clone();
+            if let Some(layout) = loaded.layout.as_ref() {
+                let problems = validation::check_flows(layout, |path| PATHS.find(path).is_some());
+                let flow_issues = layout_issues(file, layout, problems);
+                file_issues.extend(flow_issues);
+            }
+            if !file_issues.is_empty() {
+                issues.insert(file.clone(), file_issues);
+            }
+        }
+        for file in self.issues.keys() {
+            if !issues.contains_key(file) {
+                ISSUES.set_issues(file.clone(), Vec::new());
+            }
+        }
+        for (file, file_issues) in &issues {
+            if self.issues.get(file) != Some(file_issues) {
+                ISSUES.set_issues(file.clone(), file_issues.clone());
+            }
+        }
+        self.issues = issues;
+    }
+}
+
+/// Parses and validates a layout.
+fn load_layout(file: &str, format: Format, data: &str) -> LayoutFile {
+    let mut loaded = LayoutFile {
+        layout: None,
+        issues: Vec::new(),
+    };
+    match format.parse(data) {
+        Ok(layout) => {
+            let problems = validation::validate(&layout);
+            loaded.issues = layout_issues(file, &layout, problems);
+            for issue in &loaded.issues {
+                log::warn!("Invalid layout {}", issue);
+            }
+            loaded.layout = Some(layout);
+        }
+        Err(err) => {
+            let issue = LayoutIssue {
+                file: file.into(),
+                line: err.line,
+                column: err.column,
+                layout: None,
+                message: err.message,
+            };
+            log::error!("Can't parse layout file {}", issue);
+            loaded.issues.push(issue);
+        }
+    }
+    loaded
+}
+
+/// Converts problems of a layout to issues of the file.
+fn layout_issues(file: &str, layout: &Layout, problems: Vec<Problem>) -> Vec<LayoutIssue> {
+    problems
+        .into_iter()
+        .map(|problem| LayoutIssue {
+            file: file.into(),
+            line: None,
+            column: None,
+            layout: Some(layout.name.clone()),
+            message: problem.message,
+        })
+        .collect()
+}
+
 #[async_trait]
 impl ActionHandler<Reload> for ConfigWatcher {
     async fn handle(&mut self, _event: Reload, _ctx: &mut Context<Self>) -> Result<(), Error> {
@@ -214,6 +318,14 @@ impl ActionHandler<Reload> for ConfigWatcher {
     }
 }
 
+#[async_trait]
+impl Consumer<Path> for ConfigWatcher {
+    async fn handle(&mut self, _path: Path, _ctx: &mut Context<Self>) -> Result<(), Error> {
+        self.publish_issues();
+        Ok(())
+    }
+}
+
 // TODO: How about to use plain actions for `HeartBeat`?
 #[async_trait]
 impl OnTick for ConfigWatcher {
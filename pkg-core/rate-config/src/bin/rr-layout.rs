@@ -0,0 +1,31 @@
+//! Converts layout files between formats: `rr-layout <input> <output>`.
+//! Formats are detected by extensions: `xml`, `toml`, `yaml` (`yml`) and `json`.
+
+use anyhow::Error;
+use rate_config::config::format::Format;
+use rate_config::config::validation;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn format_of(path: &str) -> Result<Format, Error> {
+    Format::from_path(Path::new(path))
+        .ok_or_else(|| Error::msg(format!("Unknown format of the layout file: {}", path)))
+}
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [input, output] = args.as_slice() {
+        let data = fs::read_to_string(input)?;
+        let format = format_of(input)?;
+        let layout = format.parse(&data)?;
+        for problem in validation::validate(&layout) {
+            eprintln!("Warning: {}: {}", input, problem);
+        }
+        let data = format_of(output)?.render(&layout)?;
+        fs::write(output, data)?;
+        Ok(())
+    } else {
+        Err(Error::msg("Usage: rr-layout <input> <output>"))
+    }
+}
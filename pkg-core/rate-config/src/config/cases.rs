@@ -1,7 +1,7 @@
 use ordered_float::OrderedFloat;
 use rill_protocol::io::provider::Path;
 use rrpack_basis::manifest::layouts::components as basis;
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -48,11 +48,15 @@ impl From<BoxedElement> for basis::BoxedElement {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Layout {
-    #[serde(deserialize_with = "from_str")]
+    #[serde(deserialize_with = "from_str", serialize_with = "to_str")]
     pub name: Path,
-    #[serde(rename = "$value")]
+    #[serde(
+        rename(serialize = "element", deserialize = "$value"),
+        alias = "element"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub element: SingleBoxedElement,
 }
 
@@ -65,7 +69,7 @@ impl From<Layout> for basis::Layout {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum Element {
     Empty,
@@ -78,6 +82,10 @@ pub enum Element {
     Spacer(Spacer),
     Row(Row),
     Column(Column),
+    Grid(Grid),
+    Tabs(Tabs),
+    Section(Section),
+    SizedBox(SizedBox),
 
     // Components
     Text(Text),
@@ -97,6 +105,10 @@ impl From<Element> for basis::Element {
             Element::Spacer(value) => Self::Spacer(value.into()),
             Element::Row(value) => Self::Row(value.into()),
             Element::Column(value) => Self::Column(value.into()),
+            Element::Grid(value) => Self::Grid(value.into()),
+            Element::Tabs(value) => Self::Tabs(value.into()),
+            Element::Section(value) => Self::Section(value.into()),
+            Element::SizedBox(value) => Self::SizedBox(value.into()),
 
             // Components
             Element::Text(value) => Self::Text(value.into()),
@@ -105,7 +117,7 @@ impl From<Element> for basis::Element {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Align {
     pub alignment: Alignment,
@@ -121,10 +133,10 @@ impl From<Align> for basis::Align {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Center {
-    #[serde(rename = "$value")]
+    #[serde(rename(serialize = "child", deserialize = "$value"), alias = "child")]
     pub child: BoxedElement,
 }
 
@@ -136,9 +148,10 @@ impl From<Center> for basis::Center {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Container {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub child: SingleBoxedElement,
 }
 
@@ -150,7 +163,7 @@ impl From<Container> for basis::Container {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Expanded {
     pub child: BoxedElement,
@@ -166,10 +179,12 @@ impl From<Expanded> for basis::Expanded {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Spacer {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flex: Option<OrderedFloat<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub maintenance: Option<bool>,
 }
 
@@ -183,10 +198,14 @@ impl From<Spacer> for basis::Spacer {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Row {
-    #[serde(rename = "$value")]
+    #[serde(
+        rename(serialize = "children", deserialize = "$value"),
+        alias = "children"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub children: MultiBoxedElement,
 }
 
@@ -198,10 +217,14 @@ impl From<Row> for basis::Row {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Column {
-    #[serde(rename = "$value")]
+    #[serde(
+        rename(serialize = "children", deserialize = "$value"),
+        alias = "children"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub children: MultiBoxedElement,
 }
 
@@ -213,7 +236,132 @@ impl From<Column> for basis::Column {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Grid {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<u16>,
+    #[serde(rename(serialize = "items", deserialize = "$value"), alias = "items")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<GridItem>>,
+}
+
+impl From<Grid> for basis::Grid {
+    fn from(value: Grid) -> Self {
+        let items = value.items.unwrap_or_default();
+        Self {
+            // A single row by default
+            columns: value.columns.unwrap_or(items.len().max(1) as u16),
+            items: items.into_iter().map(basis::GridItem::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GridItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub col_span: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_span: Option<u16>,
+    #[serde(rename(serialize = "child", deserialize = "$value"), alias = "child")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child: SingleBoxedElement,
+}
+
+impl From<GridItem> for basis::GridItem {
+    fn from(value: GridItem) -> Self {
+        Self {
+            col_span: value.col_span.unwrap_or(1),
+            row_span: value.row_span.unwrap_or(1),
+            child: *unpack_single(value.child),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Tabs {
+    #[serde(rename(serialize = "tabs", deserialize = "$value"), alias = "tabs")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<Vec<Tab>>,
+}
+
+impl From<Tabs> for basis::Tabs {
+    fn from(value: Tabs) -> Self {
+        Self {
+            tabs: value
+                .tabs
+                .unwrap_or_default()
+                .into_iter()
+                .map(basis::Tab::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Tab {
+    pub title: String,
+    #[serde(rename(serialize = "child", deserialize = "$value"), alias = "child")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child: SingleBoxedElement,
+}
+
+impl From<Tab> for basis::Tab {
+    fn from(value: Tab) -> Self {
+        Self {
+            title: value.title,
+            child: *unpack_single(value.child),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Section {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<bool>,
+    #[serde(rename(serialize = "child", deserialize = "$value"), alias = "child")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child: SingleBoxedElement,
+}
+
+impl From<Section> for basis::Section {
+    fn from(value: Section) -> Self {
+        Self {
+            title: value.title,
+            collapsed: value.collapsed.unwrap_or_default(),
+            child: unpack_single(value.child),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SizedBox {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(rename(serialize = "child", deserialize = "$value"), alias = "child")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child: SingleBoxedElement,
+}
+
+impl From<SizedBox> for basis::SizedBox {
+    fn from(value: SizedBox) -> Self {
+        Self {
+            width: value.width,
+            height: value.height,
+            child: unpack_single(value.child),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Alignment {
     pub x: OrderedFloat<f64>,
@@ -229,11 +377,12 @@ impl From<Alignment> for basis::Alignment {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Text {
-    #[serde(rename = "$value")]
+    #[serde(rename(serialize = "text", deserialize = "$value"), alias = "text")]
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub align: Option<TextAlign>,
 }
 
@@ -246,7 +395,7 @@ impl From<Text> for basis::Text {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum TextAlign {
     Left = 0,
@@ -277,16 +426,19 @@ impl From<TextAlign> for basis::TextAlign {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Flow {
-    #[serde(deserialize_with = "from_str")]
+    #[serde(deserialize_with = "from_str", serialize_with = "to_str")]
     pub path: Path,
 }
 
 impl From<Flow> for basis::Flow {
     fn from(value: Flow) -> Self {
-        Self { path: value.path }
+        Self {
+            path: value.path,
+            size: None,
+        }
     }
 }
 
@@ -299,3 +451,122 @@ where
     let s = <String>::deserialize(deserializer)?;
     T::from_str(&s).map_err(Error::custom)
 }
+
+pub fn to_str<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(xml: &str) -> basis::Layout {
+        let layout: Layout = serde_xml_rs::from_str(xml).unwrap();
+        layout.into()
+    }
+
+    fn flow(path: &str) -> basis::Element {
+        basis::Flow {
+            path: path.parse().unwrap(),
+            size: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_grid() {
+        let layout = parse(
+            r#"
+            <layout name="grid">
+                <grid columns="2">
+                    <grid-item col-span="2"><flow path="a.b" /></grid-item>
+                    <grid-item><flow path="a.c" /></grid-item>
+                </grid>
+            </layout>
+            "#,
+        );
+        let expected = basis::Grid::new(
+            2,
+            vec![
+                basis::GridItem::spanned(flow("a.b"), 2, 1),
+                basis::GridItem::new(flow("a.c")),
+            ],
+        );
+        assert_eq!(layout.element, expected.into());
+    }
+
+    #[test]
+    fn test_grid_defaults() {
+        let layout = parse(
+            r#"
+            <layout name="grid">
+                <grid>
+                    <grid-item><flow path="a.b" /></grid-item>
+                    <grid-item><flow path="a.c" /></grid-item>
+                    <grid-item><flow path="a.d" /></grid-item>
+                </grid>
+            </layout>
+            "#,
+        );
+        match layout.element {
+            basis::Element::Grid(grid) => {
+                assert_eq!(grid.columns, 3);
+                assert_eq!(grid.items.len(), 3);
+            }
+            other => panic!("not a grid: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tabs() {
+        let layout = parse(
+            r#"
+            <layout name="tabs">
+                <tabs>
+                    <tab title="First"><flow path="a.b" /></tab>
+                    <tab title="Second"><flow path="a.c" /></tab>
+                </tabs>
+            </layout>
+            "#,
+        );
+        let expected = basis::Tabs::new(vec![
+            basis::Tab::new("First", flow("a.b")),
+            basis::Tab::new("Second", flow("a.c")),
+        ]);
+        assert_eq!(layout.element, expected.into());
+    }
+
+    #[test]
+    fn test_section() {
+        let layout = parse(
+            r#"
+            <layout name="section">
+                <section title="Details" collapsed="true">
+                    <flow path="a.b" />
+                </section>
+            </layout>
+            "#,
+        );
+        let expected = basis::Section::new("Details", flow("a.b")).collapsed(true);
+        assert_eq!(layout.element, expected.into());
+    }
+
+    #[test]
+    fn test_sized_box() {
+        let layout = parse(
+            r#"
+            <layout name="sized">
+                <sized-box width="300">
+                    <flow path="a.b" />
+                </sized-box>
+            </layout>
+            "#,
+        );
+        let expected = basis::SizedBox::new(Some(300), None, flow("a.b"));
+        assert_eq!(layout.element, expected.into());
+    }
+}
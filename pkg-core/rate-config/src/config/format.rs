@@ -0,0 +1,526 @@
+//! Formats of layout files.
+
+use super::cases::{Element, Layout, TextAlign};
+use std::fmt::{self, Display, Write};
+use std::path::Path;
+use xml::common::Position;
+
+/// An error of parsing with the position (starts from `1`) if it's known.
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+impl ParseError {
+    fn new(err: impl Display, position: Option<(u64, u64)>) -> Self {
+        Self {
+            message: err.to_string(),
+            line: position.map(|(line, _)| line as u32),
+            column: position.map(|(_, column)| column as u32),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A format of a layout file detected by the extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Xml,
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_ref() {
+            "xml" => Some(Self::Xml),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, data: &str) -> Result<Layout, ParseError> {
+        match self {
+            Self::Xml => serde_xml_rs::from_str(data).map_err(|err| {
+                // Only syntax errors have positions
+                let position = match &err {
+                    serde_xml_rs::Error::Syntax { source } => {
+                        let pos = source.position();
+                        Some((pos.row + 1, pos.column + 1))
+                    }
+                    _ => None,
+                };
+                ParseError::new(err, position)
+            }),
+            Self::Toml => toml::from_str(data).or_else(|err| {
+                // `toml` can't read enums declared with table headers,
+                // they are supported through other formats only.
+                let value: toml::Value = toml::from_str(data)
+                    .map_err(|err| ParseError::new(&err, toml_position(&err)))?;
+                serde_json::to_value(value)
+                    .and_then(serde_json::from_value)
+                    .map_err(|json_err| match toml_position(&err) {
+                        // The position of the header limitation is the start of the document
+                        Some((1, 1)) => ParseError::new(json_err, None),
+                        position => ParseError::new(&err, position),
+                    })
+            }),
+            Self::Yaml => serde_yaml::from_str(data).map_err(|err| {
+                let position = err
+                    .location()
+                    .map(|loc| (loc.line() as u64, loc.column() as u64));
+                ParseError::new(err, position)
+            }),
+            Self::Json => serde_json::from_str(data).map_err(|err| {
+                let position = (err.line() as u64, err.column() as u64);
+                ParseError::new(err, Some(position))
+            }),
+        }
+    }
+
+    pub fn render(&self, layout: &Layout) -> Result<String, anyhow::Error> {
+        let data = match self {
+            Self::Xml => {
+                let mut xml = XmlWriter::default();
+                xml.layout(layout);
+                xml.data
+            }
+            Self::Toml => {
+                // `toml` can't write unit variants, that's why a value is used
+                let value = toml::Value::try_from(serde_json::to_value(layout)?)?;
+                let mut toml = TomlWriter::default();
+                toml.document(&value);
+                toml.data
+            }
+            Self::Yaml => serde_yaml::to_string(layout)?,
+            Self::Json => serde_json::to_string_pretty(layout)?,
+        };
+        Ok(data)
+    }
+}
+
+fn toml_position(err: &toml::de::Error) -> Option<(u64, u64)> {
+    err.line_col()
+        .map(|(line, column)| (line as u64 + 1, column as u64 + 1))
+}
+
+/// Writes layouts with inline tables of elements.
+///
+/// `toml` reads enums from inline tables only and can't write them,
+/// arrays of elements are split to lines to keep files readable.
+#[derive(Default)]
+struct TomlWriter {
+    data: String,
+    depth: usize,
+}
+
+impl TomlWriter {
+    fn document(&mut self, value: &toml::Value) {
+        if let toml::Value::Table(table) = value {
+            // Plain values go before tables like in the regular format
+            let (tables, values): (Vec<_>, Vec<_>) =
+                table.iter().partition(|(_, value)| value.is_table());
+            for (key, value) in values.into_iter().chain(tables) {
+                self.key(key);
+                self.data.push_str(" = ");
+                self.value(value);
+                self.data.push('\n');
+            }
+        }
+    }
+
+    fn value(&mut self, value: &toml::Value) {
+        match value {
+            toml::Value::Table(table) if table.is_empty() => self.data.push_str("{}"),
+            toml::Value::Table(table) => {
+                self.data.push_str("{ ");
+                for (idx, (key, value)) in table.iter().enumerate() {
+                    if idx > 0 {
+                        self.data.push_str(", ");
+                    }
+                    self.key(key);
+                    self.data.push_str(" = ");
+                    self.value(value);
+                }
+                self.data.push_str(" }");
+            }
+            toml::Value::Array(items) if items.iter().any(|item| item.is_table()) => {
+                self.data.push_str("[\n");
+                self.depth += 1;
+                for item in items {
+                    self.indent();
+                    self.value(item);
+                    self.data.push_str(",\n");
+                }
+                self.depth -= 1;
+                self.indent();
+                self.data.push(']');
+            }
+            toml::Value::Array(items) => {
+                self.data.push('[');
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        self.data.push_str(", ");
+                    }
+                    self.value(item);
+                }
+                self.data.push(']');
+            }
+            value => {
+                write!(self.data, "{}", value).ok();
+            }
+        }
+    }
+
+    fn key(&mut self, key: &str) {
+        let bare = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if bare {
+            self.data.push_str(key);
+        } else {
+            write!(self.data, "{}", toml::Value::from(key)).ok();
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.data.push_str("    ");
+        }
+    }
+}
+
+/// Writes layouts in the same shape that the XML parser expects.
+///
+/// `serde-xml-rs` can't write attributes, that's why it's implemented manually.
+#[derive(Default)]
+struct XmlWriter {
+    data: String,
+    depth: usize,
+}
+
+type Attrs<'a> = Vec<(&'a str, Option<String>)>;
+
+fn attr(name: &str, value: Option<impl Display>) -> (&str, Option<String>) {
+    (name, value.map(|value| value.to_string()))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl XmlWriter {
+    fn layout(&mut self, layout: &Layout) {
+        let attrs = vec![attr("name", Some(&layout.name))];
+        self.tag("layout", attrs, |xml| {
+            if let Some(element) = layout.element.as_ref() {
+                xml.element(element);
+            }
+        });
+    }
+
+    fn element(&mut self, element: &Element) {
+        match element {
+            Element::Empty => self.empty("empty", Vec::new()),
+            Element::Align(value) => self.tag("align", Vec::new(), |xml| {
+                let alignment = vec![
+                    attr("x", Some(value.alignment.x)),
+                    attr("y", Some(value.alignment.y)),
+                ];
+                xml.empty("alignment", alignment);
+                xml.tag("child", Vec::new(), |xml| xml.element(&value.child));
+            }),
+            Element::Center(value) => {
+                self.tag("center", Vec::new(), |xml| xml.element(&value.child));
+            }
+            Element::Container(value) => self.tag("container", Vec::new(), |xml| {
+                if let Some(child) = value.child.as_ref() {
+                    xml.tag("child", Vec::new(), |xml| xml.element(child));
+                }
+            }),
+            Element::Expanded(value) => {
+                let attrs = vec![attr("flex", Some(value.flex))];
+                self.tag("expanded", attrs, |xml| {
+                    xml.tag("child", Vec::new(), |xml| xml.element(&value.child));
+                });
+            }
+            Element::Spacer(value) => {
+                let attrs = vec![
+                    attr("flex", value.flex),
+                    attr("maintenance", value.maintenance),
+                ];
+                self.empty("spacer", attrs);
+            }
+            Element::Row(value) => self.children("row", Vec::new(), &value.children),
+            Element::Column(value) => self.children("column", Vec::new(), &value.children),
+            Element::Grid(value) => {
+                let attrs = vec![attr("columns", value.columns)];
+                self.tag("grid", attrs, |xml| {
+                    for item in value.items.iter().flatten() {
+                        let attrs = vec![
+                            attr("col-span", item.col_span),
+                            attr("row-span", item.row_span),
+                        ];
+                        xml.single("item", attrs, &item.child);
+                    }
+                });
+            }
+            Element::Tabs(value) => self.tag("tabs", Vec::new(), |xml| {
+                for tab in value.tabs.iter().flatten() {
+                    let attrs = vec![attr("title", Some(&tab.title))];
+                    xml.single("tab", attrs, &tab.child);
+                }
+            }),
+            Element::Section(value) => {
+                let attrs = vec![
+                    attr("title", Some(&value.title)),
+                    attr("collapsed", value.collapsed),
+                ];
+                self.single("section", attrs, &value.child);
+            }
+            Element::SizedBox(value) => {
+                let attrs = vec![attr("width", value.width), attr("height", value.height)];
+                self.single("sized-box", attrs, &value.child);
+            }
+            Element::Text(value) => {
+                let align = value.align.as_ref().map(|align| match align {
+                    TextAlign::Left => "left",
+                    TextAlign::Right => "right",
+                    TextAlign::Center => "center",
+                    TextAlign::Justify => "justify",
+                    TextAlign::Start => "start",
+                    TextAlign::End => "end",
+                });
+                self.indent();
+                self.open("text", vec![attr("align", align)]);
+                self.data.push_str(&escape(&value.text));
+                self.data.push_str("</text>\n");
+            }
+            Element::Flow(value) => self.empty("flow", vec![attr("path", Some(&value.path))]),
+        }
+    }
+
+    fn children(&mut self, name: &str, attrs: Attrs<'_>, children: &Option<Vec<Element>>) {
+        self.tag(name, attrs, |xml| {
+            for child in children.iter().flatten() {
+                xml.element(child);
+            }
+        });
+    }
+
+    fn single(&mut self, name: &str, attrs: Attrs<'_>, child: &Option<Box<Element>>) {
+        self.tag(name, attrs, |xml| {
+            if let Some(child) = child.as_ref() {
+                xml.element(child);
+            }
+        });
+    }
+
+    fn tag<F>(&mut self, name: &str, attrs: Attrs<'_>, content: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.indent();
+        self.open(name, attrs);
+        self.data.push('\n');
+        self.depth += 1;
+        content(self);
+        self.depth -= 1;
+        self.indent();
+        writeln!(self.data, "</{}>", name).ok();
+    }
+
+    fn empty(&mut self, name: &str, attrs: Attrs<'_>) {
+        self.indent();
+        self.open(name, attrs);
+        // Replaces `>` of the opening tag
+        self.data.pop();
+        self.data.push_str(" />\n");
+    }
+
+    fn open(&mut self, name: &str, attrs: Attrs<'_>) {
+        write!(self.data, "<{}", name).ok();
+        for (key, value) in attrs {
+            if let Some(value) = value {
+                write!(self.data, " {}=\"{}\"", key, escape(&value)).ok();
+            }
+        }
+        self.data.push('>');
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.data.push_str("    ");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::cases::Text;
+
+    const LAYOUT: &str = r#"<layout name="test.layout">
+    <column>
+        <align>
+            <alignment x="0.5" y="-1" />
+            <child>
+                <center>
+                    <flow path="test.flow.one" />
+                </center>
+            </child>
+        </align>
+        <container>
+            <child>
+                <empty />
+            </child>
+        </container>
+        <container>
+        </container>
+        <expanded flex="2">
+            <child>
+                <text align="right">Tom &amp; Jerry &lt;&quot;quoted&quot;&gt;</text>
+            </child>
+        </expanded>
+        <row>
+            <spacer flex="1.5" maintenance="true" />
+            <spacer />
+            <text>plain</text>
+        </row>
+        <grid columns="3">
+            <item col-span="2" row-span="1">
+                <flow path="test.flow.two" />
+            </item>
+            <item>
+                <empty />
+            </item>
+            <item col-span="1">
+            </item>
+        </grid>
+        <tabs>
+            <tab title="First &amp; &quot;main&quot;">
+                <section title="Details" collapsed="true">
+                    <sized-box width="100" height="20">
+                        <flow path="test.flow.three" />
+                    </sized-box>
+                </section>
+            </tab>
+            <tab title="Second">
+                <sized-box height="10">
+                </sized-box>
+            </tab>
+        </tabs>
+    </column>
+</layout>
+"#;
+
+    fn round_trip(format: Format) {
+        let layout = Format::Xml.parse(LAYOUT).unwrap();
+        let data = format.render(&layout).unwrap();
+        let parsed = format.parse(&data).unwrap();
+        assert_eq!(parsed, layout, "{:?} data:\n{}", format, data);
+        let xml = Format::Xml.render(&parsed).unwrap();
+        assert_eq!(xml, LAYOUT);
+        assert_eq!(Format::Xml.parse(&xml).unwrap(), layout);
+    }
+
+    #[test]
+    fn test_xml_render() {
+        let layout = Format::Xml.parse(LAYOUT).unwrap();
+        assert_eq!(Format::Xml.render(&layout).unwrap(), LAYOUT);
+    }
+
+    #[test]
+    fn test_xml_escaping() {
+        let layout = Format::Xml.parse(LAYOUT).unwrap();
+        let children = match layout.element.as_deref() {
+            Some(Element::Column(column)) => column.children.clone().unwrap(),
+            other => panic!("unexpected element: {:?}", other),
+        };
+        let text = match &children[3] {
+            Element::Expanded(expanded) => expanded.child.as_ref().clone(),
+            other => panic!("unexpected element: {:?}", other),
+        };
+        let expected = Text {
+            text: "Tom & Jerry <\"quoted\">".into(),
+            align: Some(TextAlign::Right),
+        };
+        assert_eq!(text, Element::Text(expected));
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        round_trip(Format::Toml);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        round_trip(Format::Yaml);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        round_trip(Format::Json);
+    }
+
+    #[test]
+    fn test_toml_table_headers() {
+        let data = "name = \"test.layout\"\n\n[element.row]\nchildren = [\"empty\"]\n";
+        let layout = Format::Toml.parse(data).unwrap();
+        let expected = Format::Xml
+            .parse(r#"<layout name="test.layout"><row><empty /></row></layout>"#)
+            .unwrap();
+        assert_eq!(layout, expected);
+    }
+
+    fn error_position(format: Format, data: &str) -> (Option<u32>, Option<u32>) {
+        let err = format.parse(data).unwrap_err();
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn test_xml_error_position() {
+        // Unknown tags have no positions
+        let data = "<layout name=\"test.layout\">\n    <row>\n        <unknown />\n";
+        assert_eq!(error_position(Format::Xml, data), (None, None));
+        let data = "<layout name=\"test.layout\">\n    <row>\n    </column>\n";
+        assert_eq!(error_position(Format::Xml, data), (Some(3), Some(13)));
+    }
+
+    #[test]
+    fn test_yaml_error_position() {
+        let data = "---\nname: test.layout\nelement:\n  row: {children: [empty}\n";
+        assert_eq!(error_position(Format::Yaml, data), (Some(4), Some(25)));
+    }
+
+    #[test]
+    fn test_json_error_position() {
+        let data = "{\n  \"name\": \"test.layout\",\n  \"element\": \"unknown\"\n}";
+        assert_eq!(error_position(Format::Json, data), (Some(3), Some(22)));
+    }
+
+    #[test]
+    fn test_toml_error_position() {
+        let data = "name = \"test.layout\"\nelement = { grid = { columns = \"3\" } }\n";
+        let err = Format::Toml.parse(data).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(32)));
+    }
+}
@@ -1,4 +1,7 @@
 //! Configs
 
 pub mod cases;
+pub mod format;
+pub mod notifier;
 pub mod server;
+pub mod validation;
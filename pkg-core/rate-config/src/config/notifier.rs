@@ -0,0 +1,47 @@
+//! Sinks of alert notifications.
+
+pub use rrpack_prime::transparent::alert::Severity;
+use serde::Deserialize;
+
+/// A sink of alert notifications.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum NotifierConfig {
+    /// Sends a `POST` request.
+    Webhook {
+        url: String,
+        /// A JSON template with `{{field}}` placeholders.
+        /// The notification is sent as JSON if it's not set.
+        body: Option<String>,
+        #[serde(flatten)]
+        delivery: DeliveryConfig,
+    },
+    /// Executes a local command with `RR_ALERT_*` env vars.
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(flatten)]
+        delivery: DeliveryConfig,
+    },
+}
+
+impl NotifierConfig {
+    pub fn delivery(&self) -> &DeliveryConfig {
+        match self {
+            Self::Webhook { delivery, .. } => delivery,
+            Self::Command { delivery, .. } => delivery,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeliveryConfig {
+    /// Notifications with a lower severity are skipped.
+    pub min_severity: Option<Severity>,
+    /// Max amount of notifications per minute.
+    pub rate_limit: Option<u32>,
+    /// How many times to retry a failed delivery.
+    pub retries: Option<u32>,
+}
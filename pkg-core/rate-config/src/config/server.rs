@@ -1,5 +1,6 @@
 //! This module contains the main config.
 
+use super::notifier::NotifierConfig;
 use rate_core::actors::node::NodeConfig;
 use rill_config::{Config, ReadableConfig};
 use serde::Deserialize;
@@ -10,6 +11,8 @@ pub struct RillRateConfig {
     pub node: Option<NodeConfig>,
     /// Show explorer
     pub explorer: Option<bool>,
+    /// Sinks of alert notifications
+    pub notifier: Option<Vec<NotifierConfig>>,
 }
 
 impl Config for RillRateConfig {}
@@ -0,0 +1,242 @@
+//! Semantic checks of layouts that can't be expressed by the schema.
+
+use super::cases::{Alignment, Element, Layout};
+use ordered_float::OrderedFloat;
+use rill_protocol::io::provider::Path;
+use rrpack_basis::manifest::layouts::components as basis;
+use std::fmt;
+
+/// A problem of a layout with the field that causes it.
+///
+/// Parsers don't report positions of values, that's why problems have no locations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub message: String,
+    pub field: &'static str,
+}
+
+impl Problem {
+    fn new(message: String, field: &'static str) -> Self {
+        Self { message, field }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+/// Returns all problems of the layout.
+pub fn validate(layout: &Layout) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    if is_blank(&layout.name) {
+        problems.push(Problem::new("layout has an empty name".into(), "name"));
+    }
+    if let Some(element) = layout.element.as_ref() {
+        check(element, &mut problems);
+    }
+    problems
+}
+
+/// Returns problems of flows that are not `known`.
+///
+/// Flows are registered at runtime, that's why they are checked separately.
+pub fn check_flows<F>(layout: &Layout, known: F) -> Vec<Problem>
+where
+    F: Fn(&Path) -> bool,
+{
+    let layout = basis::Layout::from(layout.clone());
+    layout
+        .element
+        .flows()
+        .into_iter()
+        // Empty paths are reported by `validate`
+        .filter(|flow| !is_blank(&flow.path) && !known(&flow.path))
+        .map(|flow| {
+            let message = format!("flow: `path` {} is missing", flow.path);
+            Problem::new(message, "path")
+        })
+        .collect()
+}
+
+/// An empty string is parsed to a path with an empty entry.
+fn is_blank(path: &Path) -> bool {
+    path.iter().all(|entry| entry.as_ref().is_empty())
+}
+
+fn check(element: &Element, problems: &mut Vec<Problem>) {
+    match element {
+        Element::Empty | Element::Text(_) => {}
+        Element::Align(value) => {
+            check_alignment(&value.alignment, problems);
+            check(&value.child, problems);
+        }
+        Element::Center(value) => check(&value.child, problems),
+        Element::Container(value) => value.child.iter().for_each(|child| check(child, problems)),
+        Element::Expanded(value) => {
+            check_flex("expanded", Some(value.flex), problems);
+            check(&value.child, problems);
+        }
+        Element::Spacer(value) => check_flex("spacer", value.flex, problems),
+        Element::Row(value) => value
+            .children
+            .iter()
+            .flatten()
+            .for_each(|child| check(child, problems)),
+        Element::Column(value) => value
+            .children
+            .iter()
+            .flatten()
+            .for_each(|child| check(child, problems)),
+        Element::Grid(value) => {
+            if value.columns == Some(0) {
+                let message = "grid: `columns` must be greater than 0".into();
+                problems.push(Problem::new(message, "columns"));
+            }
+            for item in value.items.iter().flatten() {
+                let spans = [("col-span", item.col_span), ("row-span", item.row_span)];
+                for (name, span) in spans.iter() {
+                    if *span == Some(0) {
+                        let message = format!("grid item: `{}` must be greater than 0", name);
+                        problems.push(Problem::new(message, name));
+                    }
+                }
+                if let (Some(span), Some(columns)) = (item.col_span, value.columns) {
+                    if span > columns {
+                        let message = format!(
+                            "grid item: `col-span` {} is greater than `columns` {}",
+                            span, columns
+                        );
+                        problems.push(Problem::new(message, "col-span"));
+                    }
+                }
+                item.child.iter().for_each(|child| check(child, problems));
+            }
+        }
+        Element::Tabs(value) => {
+            for tab in value.tabs.iter().flatten() {
+                if tab.title.is_empty() {
+                    problems.push(Problem::new("tab: `title` is empty".into(), "title"));
+                }
+                tab.child.iter().for_each(|child| check(child, problems));
+            }
+        }
+        Element::Section(value) => value.child.iter().for_each(|child| check(child, problems)),
+        Element::SizedBox(value) => value.child.iter().for_each(|child| check(child, problems)),
+        Element::Flow(value) => {
+            if is_blank(&value.path) {
+                problems.push(Problem::new("flow: `path` is empty".into(), "path"));
+            }
+        }
+    }
+}
+
+fn check_alignment(alignment: &Alignment, problems: &mut Vec<Problem>) {
+    let range = OrderedFloat(-1.0)..=OrderedFloat(1.0);
+    let bad = [("x", alignment.x), ("y", alignment.y)]
+        .iter()
+        .find(|(_, value)| !range.contains(value))
+        .copied();
+    if let Some((field, _)) = bad {
+        let message = format!(
+            "align: bad alignment ({}, {}), values must be in the range -1..1",
+            alignment.x, alignment.y
+        );
+        problems.push(Problem::new(message, field));
+    }
+}
+
+fn check_flex(name: &str, flex: Option<OrderedFloat<f64>>, problems: &mut Vec<Problem>) {
+    if let Some(flex) = flex {
+        if flex < OrderedFloat(0.0) {
+            let message = format!("{}: `flex` {} must not be negative", name, flex);
+            problems.push(Problem::new(message, "flex"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::format::Format;
+
+    const LAYOUT: &str = r#"<layout name="test.layout">
+    <column>
+        <align>
+            <alignment x="0" y="2" />
+            <child>
+                <spacer flex="-1" />
+            </child>
+        </align>
+        <grid columns="2">
+            <item col-span="3">
+                <flow path="test.flow.known" />
+            </item>
+            <item row-span="0">
+                <flow path="" />
+            </item>
+        </grid>
+        <tabs>
+            <tab title="">
+                <flow path="test.flow.missing" />
+            </tab>
+            <tab title="Second">
+                <flow path="test.flow.missing" />
+            </tab>
+        </tabs>
+    </column>
+</layout>
+"#;
+
+    fn messages(problems: &[Problem]) -> Vec<&str> {
+        problems
+            .iter()
+            .map(|problem| problem.message.as_ref())
+            .collect()
+    }
+
+    fn known(path: &Path) -> bool {
+        path == &"test.flow.known".parse::<Path>().unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let layout = Format::Xml.parse(LAYOUT).unwrap();
+        let problems = validate(&layout);
+        let expected = vec![
+            "align: bad alignment (0, 2), values must be in the range -1..1",
+            "spacer: `flex` -1 must not be negative",
+            "grid item: `col-span` 3 is greater than `columns` 2",
+            "grid item: `row-span` must be greater than 0",
+            "flow: `path` is empty",
+            "tab: `title` is empty",
+        ];
+        assert_eq!(messages(&problems), expected);
+        let fields: Vec<_> = problems.iter().map(|problem| problem.field).collect();
+        assert_eq!(
+            fields,
+            vec!["y", "flex", "col-span", "row-span", "path", "title"]
+        );
+    }
+
+    #[test]
+    fn test_validate_valid() {
+        let data = r#"<layout name="test.layout"><flow path="test.flow.known" /></layout>"#;
+        let layout = Format::Xml.parse(data).unwrap();
+        assert!(validate(&layout).is_empty());
+        assert!(check_flows(&layout, known).is_empty());
+    }
+
+    #[test]
+    fn test_check_flows() {
+        let layout = Format::Xml.parse(LAYOUT).unwrap();
+        let problems = check_flows(&layout, known);
+        let expected = vec![
+            "flow: `path` test.flow.missing is missing",
+            "flow: `path` test.flow.missing is missing",
+        ];
+        assert_eq!(messages(&problems), expected);
+        assert!(check_flows(&layout, |_| true).is_empty());
+    }
+}
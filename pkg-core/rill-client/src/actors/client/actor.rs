@@ -1,5 +1,10 @@
+mod requests;
+mod subscription;
 mod wait_ready;
 
+pub use requests::RequestError;
+pub use subscription::StateStream;
+
 use anyhow::Error;
 use async_trait::async_trait;
 use derive_more::From;
@@ -12,10 +17,12 @@ use meio_connect::{
     WsIncoming,
 };
 use rill_protocol::io::client::{
-    ClientProtocol, ClientRequest, ClientResponse, ClientServiceRequest, ClientServiceResponse,
+    AccessLevel, ClientProtocol, ClientReqId, ClientRequest, ClientResponse, ClientServiceRequest,
+    ClientServiceResponse,
 };
-use rill_protocol::io::transport::ServiceEnvelope;
-use std::collections::VecDeque;
+use rill_protocol::io::provider::{FlowControl, RecorderRequest};
+use rill_protocol::io::transport::{Envelope, ServiceEnvelope};
+use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 
 type WsOutgoing = WsSender<ServiceEnvelope<ClientProtocol, ClientRequest, ClientServiceResponse>>;
@@ -28,7 +35,11 @@ pub struct RillClientLink {
 pub struct RillClient {
     url: String,
     sender: Option<WsOutgoing>,
+    /// Requests are sent when the server grants an access level.
+    access_level: Option<AccessLevel>,
     awaiting_clients: VecDeque<wait_ready::Notifier>,
+    next_id: usize,
+    directions: HashMap<ClientReqId, requests::Direction>,
 }
 
 impl RillClient {
@@ -37,7 +48,61 @@ impl RillClient {
         Self {
             url,
             sender: None,
+            access_level: None,
             awaiting_clients: VecDeque::new(),
+            next_id: 0,
+            directions: HashMap::new(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.sender.is_some() && self.access_level.is_some()
+    }
+
+    fn send_request(&mut self, direct_id: ClientReqId, request: ClientRequest) {
+        if let Some(sender) = self.sender.as_ref() {
+            let envelope = Envelope {
+                direct_id,
+                data: request,
+            };
+            sender.send(ServiceEnvelope::Envelope(envelope));
+        } else {
+            log::error!("Can't send a request. Not connected.");
+        }
+    }
+
+    /// Sends requests that wait for the connection or were
+    /// interrupted by a reconnection.
+    fn send_pending(&mut self) {
+        for (direct_id, request) in requests::pending_requests(&mut self.directions) {
+            self.send_request(direct_id, request);
+        }
+    }
+
+    fn forward_response(&mut self, direct_id: ClientReqId, response: ClientResponse) {
+        let direction = match self.directions.get_mut(&direct_id) {
+            Some(direction) => direction,
+            None => {
+                log::warn!("Response for an unknown request {:?}", direct_id);
+                return;
+            }
+        };
+        let done = matches!(response, ClientResponse::Done);
+        let delivered = direction
+            .sender
+            .as_ref()
+            .map(|sender| sender.send(response).is_ok())
+            .unwrap_or_default();
+        if done || !direction.stream {
+            self.directions.remove(&direct_id);
+        } else if !delivered && !direction.stopped {
+            // The stream was dropped by a client
+            direction.stopped = true;
+            let request = ClientRequest {
+                path: direction.path.clone(),
+                request: RecorderRequest::ControlStream(FlowControl::StopStream),
+            };
+            self.send_request(direct_id, request);
         }
     }
 }
@@ -86,11 +151,14 @@ impl InstantActionHandler<WsClientStatus<ClientProtocol>> for RillClient {
         match status {
             WsClientStatus::Connected { sender } => {
                 self.sender = Some(sender);
+                // Wait for a new access level of the session
+                self.access_level.take();
                 self.notify_awaiting_clients();
             }
             WsClientStatus::Failed { reason } => {
                 log::error!("Connection failed: {}", reason);
                 self.sender.take();
+                self.access_level.take();
             }
         }
         Ok(())
@@ -109,19 +177,21 @@ impl
     ) -> Result<(), Error> {
         log::trace!("Incoming to exporter: {:?}", msg);
         match msg.0 {
-            ServiceEnvelope::Envelope(envelope) => {
-                let _direct_id = envelope.direct_id;
-                match envelope.data {
-                    ClientResponse::Declare(entry_id) => {
-                        log::info!("Connected to: {}", entry_id);
-                    }
-                    evt => {
-                        log::error!("Not implemented for {:?}", evt);
-                    }
+            ServiceEnvelope::Envelope(envelope) => match envelope.data {
+                ClientResponse::Declare(entry_id) => {
+                    log::info!("Connected to: {}", entry_id);
+                }
+                response => {
+                    self.forward_response(envelope.direct_id, response);
+                }
+            },
+            ServiceEnvelope::Service(ClientServiceRequest::AccessLevel(level)) => {
+                log::debug!("Access level: {:?}", level);
+                let was_ready = self.is_ready();
+                self.access_level = Some(level);
+                if !was_ready {
+                    self.send_pending();
                 }
-            }
-            ServiceEnvelope::Service(_) => {
-                log::error!("Service message is not supported yet.");
             }
         }
         Ok(())
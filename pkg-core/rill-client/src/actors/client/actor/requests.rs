@@ -0,0 +1,244 @@
+use super::subscription::StateStream;
+use super::RillClient;
+use super::RillClientLink;
+use anyhow::Error;
+use async_trait::async_trait;
+use meio::{Action, ActionHandler, Context};
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::client::{ClientReqId, ClientRequest, ClientResponse};
+use rill_protocol::io::provider::{
+    Description, FlowControl, Path, RecorderAction, RecorderRequest,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::timeout;
+
+/// How long to wait for a response to a request.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(super) type ResponseSender = mpsc::UnboundedSender<ClientResponse>;
+pub(super) type ResponseReceiver = mpsc::UnboundedReceiver<ClientResponse>;
+
+#[derive(Debug, Error)]
+pub enum RequestError {
+    #[error("the client was terminated")]
+    Terminated,
+    #[error("the client is not connected")]
+    NotConnected,
+    #[error("no response in {0:?}")]
+    Timeout(Duration),
+    #[error("the request failed: {0}")]
+    Failed(String),
+    #[error("unexpected response: {0:?}")]
+    Unexpected(ClientResponse),
+}
+
+/// An active request to the server.
+pub(super) struct Direction {
+    pub path: Path,
+    pub request: RecorderRequest,
+    /// `None` for requests without responses (actions).
+    pub sender: Option<ResponseSender>,
+    /// Streams receive responses until `Done`, other requests
+    /// receive the single response.
+    pub stream: bool,
+    /// `StopStream` was sent for the stream.
+    pub stopped: bool,
+}
+
+impl Direction {
+    pub fn to_request(&self) -> ClientRequest {
+        ClientRequest {
+            path: self.path.clone(),
+            request: self.request.clone(),
+        }
+    }
+}
+
+/// Returns requests to send again after a reconnection.
+///
+/// Streams stopped by a client are removed, because they don't exist
+/// after the reconnection. Actions are removed after sending,
+/// since they have no responses.
+pub(super) fn pending_requests(
+    directions: &mut HashMap<ClientReqId, Direction>,
+) -> Vec<(ClientReqId, ClientRequest)> {
+    directions.retain(|_, direction| !direction.stopped);
+    let requests = directions
+        .iter()
+        .map(|(direct_id, direction)| (*direct_id, direction.to_request()))
+        .collect();
+    directions.retain(|_, direction| direction.sender.is_some());
+    requests
+}
+
+pub struct OpenRequest {
+    path: Path,
+    request: RecorderRequest,
+    sender: Option<ResponseSender>,
+    stream: bool,
+    /// Requests other than streams are rejected while the client is not connected.
+    accepted: oneshot::Sender<Result<(), RequestError>>,
+}
+
+impl Action for OpenRequest {}
+
+impl RillClientLink {
+    async fn send_open(
+        &mut self,
+        path: Path,
+        request: RecorderRequest,
+        sender: Option<ResponseSender>,
+        stream: bool,
+    ) -> Result<(), Error> {
+        let (accepted, rx) = oneshot::channel();
+        let msg = OpenRequest {
+            path,
+            request,
+            sender,
+            stream,
+            accepted,
+        };
+        self.address.act(msg).await?;
+        rx.await.map_err(|_| RequestError::Terminated)??;
+        Ok(())
+    }
+
+    async fn open(
+        &mut self,
+        path: Path,
+        request: RecorderRequest,
+        stream: bool,
+    ) -> Result<ResponseReceiver, Error> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.send_open(path, request, Some(tx), stream).await?;
+        Ok(rx)
+    }
+
+    async fn fetch(&mut self, path: Path, action: RecorderAction) -> Result<ClientResponse, Error> {
+        let request = RecorderRequest::Action(action);
+        let mut rx = self.open(path, request, false).await?;
+        let response = timeout(REQUEST_TIMEOUT, rx.recv())
+            .await
+            .map_err(|_| RequestError::Timeout(REQUEST_TIMEOUT))?;
+        match response {
+            Some(ClientResponse::Error(reason)) => Err(RequestError::Failed(reason).into()),
+            Some(response) => Ok(response),
+            None => Err(RequestError::Terminated.into()),
+        }
+    }
+
+    /// Subscribes to the flow. The stream yields the state of the flow
+    /// after every update and ends when the flow is finished.
+    ///
+    /// The subscription is stopped when the stream is dropped.
+    pub async fn subscribe<T: Flow>(&mut self, path: Path) -> Result<StateStream<T>, Error> {
+        let request = RecorderRequest::ControlStream(FlowControl::StartStream);
+        let rx = self.open(path.clone(), request, true).await?;
+        Ok(StateStream::new(path, rx))
+    }
+
+    /// Fetches the current state of the flow.
+    ///
+    /// Fails if the client is not connected or the response
+    /// is not received in `REQUEST_TIMEOUT`.
+    pub async fn get_snapshot<T: Flow>(&mut self, path: Path) -> Result<T, Error> {
+        match self.fetch(path, RecorderAction::GetSnapshot).await? {
+            ClientResponse::State(data) => T::unpack_state(&data),
+            other => Err(RequestError::Unexpected(other).into()),
+        }
+    }
+
+    /// Fetches the description of the flow.
+    pub async fn get_flow(&mut self, path: Path) -> Result<Description, Error> {
+        match self.fetch(path, RecorderAction::GetFlow).await? {
+            ClientResponse::Flow(description) => Ok(description),
+            other => Err(RequestError::Unexpected(other).into()),
+        }
+    }
+
+    /// Sends the action to the flow. Actions have no responses.
+    ///
+    /// Fails if the client is not connected.
+    pub async fn do_action<T: Flow>(&mut self, path: Path, action: T::Action) -> Result<(), Error> {
+        let packed_action = T::pack_action(&action)?;
+        let request = RecorderRequest::Action(RecorderAction::DoAction(packed_action));
+        self.send_open(path, request, None, false).await
+    }
+}
+
+#[async_trait]
+impl ActionHandler<OpenRequest> for RillClient {
+    async fn handle(&mut self, msg: OpenRequest, _ctx: &mut Context<Self>) -> Result<(), Error> {
+        let direct_id = self.next_id.into();
+        self.next_id += 1;
+        let direction = Direction {
+            path: msg.path,
+            request: msg.request,
+            sender: msg.sender,
+            stream: msg.stream,
+            stopped: false,
+        };
+        let result = if self.is_ready() {
+            self.send_request(direct_id, direction.to_request());
+            if direction.sender.is_some() {
+                self.directions.insert(direct_id, direction);
+            }
+            Ok(())
+        } else if direction.stream {
+            // Will be sent by `send_pending` when the client is ready
+            self.directions.insert(direct_id, direction);
+            Ok(())
+        } else {
+            Err(RequestError::NotConnected)
+        };
+        if msg.accepted.send(result).is_err() {
+            log::warn!("A request was dropped before it was accepted.");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::PackedAction;
+
+    fn direction(request: RecorderRequest, responses: bool, stopped: bool) -> Direction {
+        let sender = responses.then(|| mpsc::unbounded_channel().0);
+        Direction {
+            path: Path::single("flow"),
+            request,
+            stream: false,
+            sender,
+            stopped,
+        }
+    }
+
+    #[test]
+    fn test_pending_requests() {
+        let start = RecorderRequest::ControlStream(FlowControl::StartStream);
+        let snapshot = RecorderRequest::Action(RecorderAction::GetSnapshot);
+        let action = RecorderRequest::Action(RecorderAction::DoAction(PackedAction(Vec::new())));
+        let mut directions = HashMap::new();
+        directions.insert(0.into(), direction(start.clone(), true, false));
+        directions.insert(1.into(), direction(start, true, true));
+        directions.insert(2.into(), direction(snapshot, true, false));
+        directions.insert(3.into(), direction(action, false, false));
+        let mut ids: Vec<usize> = pending_requests(&mut directions)
+            .into_iter()
+            .map(|(direct_id, _)| direct_id.into())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 2, 3]);
+        // Requests are sent again on the next reconnection
+        let mut ids: Vec<usize> = pending_requests(&mut directions)
+            .into_iter()
+            .map(|(direct_id, _)| direct_id.into())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 2]);
+    }
+}
@@ -0,0 +1,150 @@
+use super::requests::{RequestError, ResponseReceiver};
+use anyhow::Error;
+use futures::Stream;
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::client::ClientResponse;
+use rill_protocol::io::provider::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A stream of states of a subscribed flow.
+///
+/// Events received from the server are applied to the local copy
+/// of the state with `Flow::apply`.
+#[derive(Debug)]
+pub struct StateStream<T: Flow> {
+    path: Path,
+    receiver: ResponseReceiver,
+    state: Option<T>,
+}
+
+impl<T: Flow> StateStream<T> {
+    pub(super) fn new(path: Path, receiver: ResponseReceiver) -> Self {
+        Self {
+            path,
+            receiver,
+            state: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The last received state.
+    pub fn state(&self) -> Option<&T> {
+        self.state.as_ref()
+    }
+
+    fn on_response(&mut self, response: ClientResponse) -> Option<Result<T, Error>> {
+        match response {
+            ClientResponse::State(data) => {
+                let res = T::unpack_state(&data).map(|state| {
+                    self.state = Some(state.clone());
+                    state
+                });
+                Some(res)
+            }
+            ClientResponse::Delta(data) => match T::unpack_event(&data) {
+                Ok(event) => {
+                    if let Some(state) = self.state.as_mut() {
+                        state.apply(event);
+                        Some(Ok(state.clone()))
+                    } else {
+                        log::error!("Delta received before the state of {}", self.path);
+                        None
+                    }
+                }
+                Err(err) => Some(Err(err)),
+            },
+            ClientResponse::Error(reason) => Some(Err(RequestError::Failed(reason).into())),
+            other => {
+                log::error!(
+                    "Unexpected message for the stream {}: {:?}",
+                    self.path,
+                    other
+                );
+                None
+            }
+        }
+    }
+}
+
+// The state is never pinned
+impl<T: Flow> Unpin for StateStream<T> {}
+
+impl<T: Flow> Stream for StateStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match this.receiver.poll_recv(cx) {
+                Poll::Ready(Some(ClientResponse::Done)) | Poll::Ready(None) => {
+                    return Poll::Ready(None);
+                }
+                Poll::Ready(Some(response)) => {
+                    if let Some(item) = this.on_response(response) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                Poll::Pending => {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rill_protocol::flow::meta::ready_board::{Board, ReadyBoardEvent, ReadyBoardState};
+    use tokio::sync::mpsc;
+
+    fn stream() -> StateStream<ReadyBoardState> {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        StateStream::new(Path::single("boards"), rx)
+    }
+
+    fn add_board(name: &str) -> ClientResponse {
+        let event = ReadyBoardEvent::AddBoard {
+            name: name.into(),
+            board: Board {
+                description: None,
+                paths: Default::default(),
+            },
+        };
+        ClientResponse::Delta(ReadyBoardState::pack_event(&event).unwrap())
+    }
+
+    fn names(state: &ReadyBoardState) -> Vec<&str> {
+        state.entries.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_state_then_delta() {
+        let mut stream = stream();
+        let data = ReadyBoardState::new().pack_state().unwrap();
+        let state = stream.on_response(ClientResponse::State(data)).unwrap();
+        assert!(names(&state.unwrap()).is_empty());
+        let state = stream.on_response(add_board("first")).unwrap().unwrap();
+        assert_eq!(names(&state), ["first"]);
+        assert_eq!(names(stream.state().unwrap()), ["first"]);
+    }
+
+    #[test]
+    fn test_delta_before_state() {
+        let mut stream = stream();
+        // The delta is logged and skipped, since the state is not known
+        assert!(stream.on_response(add_board("first")).is_none());
+        assert!(stream.state().is_none());
+        let data = ReadyBoardState::new().pack_state().unwrap();
+        stream
+            .on_response(ClientResponse::State(data))
+            .unwrap()
+            .unwrap();
+        let state = stream.on_response(add_board("second")).unwrap().unwrap();
+        assert_eq!(names(&state), ["second"]);
+    }
+}
@@ -1,2 +1,2 @@
 mod actor;
-pub use actor::{RillClient, RillClientLink};
+pub use actor::{RequestError, RillClient, RillClientLink, StateStream};
@@ -1,3 +1,3 @@
 mod actors;
 
-pub use actors::client::{RillClient, RillClientLink};
+pub use actors::client::{RequestError, RillClient, RillClientLink, StateStream};
@@ -1,9 +1,12 @@
 pub mod callback;
 pub mod link;
+pub mod reactor;
+pub mod ticker;
 
 use crate::actors::connector::{RillConnector, RillSender};
 use crate::tracers::tracer::{
-    ActionSender, ControlEvent, EventEnvelope, TracerMode, TracerOperator,
+    ActionSender, BoxedReactor, BoxedTicker, ControlEvent, EventEnvelope, TracerMode,
+    TracerOperator,
 };
 use anyhow::Error;
 use async_trait::async_trait;
@@ -27,6 +30,8 @@ pub(crate) struct Recorder<T: core::Flow> {
 
     operator: TracerOperator<T>,
     callback: Option<ActionSender<T>>,
+    tickers: Vec<BoxedTicker<T>>,
+    reactors: Vec<BoxedReactor<T>>,
 
     subscribers: HashSet<ProviderReqId>,
 }
@@ -42,6 +47,8 @@ impl<T: core::Flow> Recorder<T> {
             sender,
             operator,
             callback: None,
+            tickers: Vec::new(),
+            reactors: Vec::new(),
             subscribers: HashSet::new(),
         }
     }
@@ -86,11 +93,19 @@ impl<T: core::Flow> Recorder<T> {
         self.sender.response(direction, response);
     }
 
+    /// Notifies tracers about the presence of subscribers.
+    fn update_activity(&mut self) {
+        let active = !self.subscribers.is_empty();
+        // Fails only if all tracers were dropped and it's not important.
+        let _ = self.operator.activity_tx.send(active);
+    }
+
     fn graceful_shutdown(&mut self, ctx: &mut Context<Self>) {
         //log::warn!("Terminating: {}", self.name());
         // No more events will be received after this point.
         self.send_end(self.all_subscribers());
         self.subscribers.clear();
+        self.update_activity();
         ctx.shutdown();
     }
 }
@@ -98,6 +113,7 @@ impl<T: core::Flow> Recorder<T> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum Group {
     HeartBeat,
+    Ticker,
     Callback,
     DataFlow,
     ServiceFlow,
@@ -242,6 +258,12 @@ impl<T: core::Flow> Consumer<ControlEvent<T>> for Recorder<T> {
             ControlEvent::DetachCallback => {
                 self.detach_callback(ctx);
             }
+            ControlEvent::AttachTicker { interval, ticker } => {
+                self.attach_ticker(interval, ticker, ctx);
+            }
+            ControlEvent::AttachReactor { reactor } => {
+                self.attach_reactor(reactor);
+            }
         }
         Ok(())
     }
@@ -352,6 +374,7 @@ impl<T: core::Flow> ActionHandler<link::DoRecorderRequest> for Recorder<T> {
                                 self.send_activity(id, Activity::Awake, None);
                             }
                             if self.subscribers.insert(id) {
+                                self.update_activity();
                                 self.send_state(id.into()).await?;
                                 self.send_activity(id, Activity::Connected, None);
                             } else {
@@ -363,6 +386,7 @@ impl<T: core::Flow> ActionHandler<link::DoRecorderRequest> for Recorder<T> {
                         }
                         FlowControl::StopStream => {
                             if self.subscribers.remove(&id) {
+                                self.update_activity();
                                 self.send_activity(id, Activity::Disconnected, None);
                                 self.send_end(id.into());
                             } else {
@@ -390,6 +414,7 @@ impl<T: core::Flow> ActionHandler<link::DoRecorderRequest> for Recorder<T> {
                     }
                     RecorderAction::DoAction(data) => {
                         let action = T::unpack_action(&data)?;
+                        self.run_reactors(&action)?;
                         let activity = Activity::Action;
                         self.send_activity(id, activity, Some(action));
                     }
@@ -437,6 +462,7 @@ impl<T: core::Flow> ActionHandler<link::ConnectionChanged> for Recorder<T> {
             Disconnected => {
                 self.sender.reset();
                 self.subscribers.clear();
+                self.update_activity();
             }
         }
         Ok(())
@@ -0,0 +1,41 @@
+use super::Recorder;
+use crate::tracers::tracer::{BoxedReactor, EventEnvelope, TracerMode};
+use anyhow::Error;
+use rill_protocol::flow::core;
+use std::sync::Weak;
+
+impl<T: core::Flow> Recorder<T> {
+    pub(super) fn attach_reactor(&mut self, reactor: BoxedReactor<T>) {
+        self.reactors.push(reactor);
+    }
+
+    pub(super) fn run_reactors(&mut self, action: &T::Action) -> Result<(), Error> {
+        let mut events = Vec::new();
+        for reactor in &mut self.reactors {
+            match &self.operator.mode {
+                TracerMode::Push { state, .. } => {
+                    events.extend(reactor(state, action));
+                }
+                TracerMode::Pull { state, .. } => {
+                    if let Some(state) = Weak::upgrade(state) {
+                        let mut state = state
+                            .lock()
+                            .map_err(|_| Error::msg("Can't lock state to call a reactor."))?;
+                        // The changed state will be sent with the next flush
+                        if let Some(event) = reactor(&*state, action) {
+                            T::apply(&mut *state, event);
+                        }
+                    }
+                }
+            }
+        }
+        for event in events {
+            let envelope = EventEnvelope {
+                direction: None,
+                event,
+            };
+            self.process_event(envelope)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Group, Recorder};
+use crate::tracers::tracer::{BoxedTicker, EventEnvelope, TracerMode};
+use anyhow::Error;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use meio::{Consumer, Context};
+use rill_protocol::flow::core;
+use std::sync::Weak;
+use std::time::Duration;
+use tokio::time::{self, MissedTickBehavior};
+use tokio_stream::wrappers::IntervalStream;
+
+/// A signal to call the ticker with the index.
+pub struct TickerTick {
+    idx: usize,
+}
+
+impl<T: core::Flow> Recorder<T> {
+    pub(super) fn attach_ticker(
+        &mut self,
+        interval: Duration,
+        ticker: BoxedTicker<T>,
+        ctx: &mut Context<Self>,
+    ) {
+        let idx = self.tickers.len();
+        self.tickers.push(ticker);
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let ticks = IntervalStream::new(interval).map(move |_| TickerTick { idx });
+        // The stream is dropped with the `Recorder`
+        ctx.attach(ticks, (), Group::Ticker);
+    }
+
+    fn run_ticker(&mut self, idx: usize) -> Result<(), Error> {
+        let ticker = self
+            .tickers
+            .get_mut(idx)
+            .ok_or_else(|| Error::msg("Ticker not found"))?;
+        let event = match &self.operator.mode {
+            TracerMode::Push { state, .. } => ticker(state),
+            TracerMode::Pull { state, .. } => {
+                if let Some(state) = Weak::upgrade(state) {
+                    let mut state = state
+                        .lock()
+                        .map_err(|_| Error::msg("Can't lock state to call a ticker."))?;
+                    // The changed state will be sent with the next flush
+                    if let Some(event) = ticker(&*state) {
+                        T::apply(&mut *state, event);
+                    }
+                }
+                None
+            }
+        };
+        if let Some(event) = event {
+            let envelope = EventEnvelope {
+                direction: None,
+                event,
+            };
+            self.process_event(envelope)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: core::Flow> Consumer<TickerTick> for Recorder<T> {
+    async fn handle(&mut self, tick: TickerTick, ctx: &mut Context<Self>) -> Result<(), Error> {
+        if !ctx.is_terminating() {
+            self.run_ticker(tick.idx)?;
+        }
+        Ok(())
+    }
+
+    async fn finished(&mut self, _ctx: &mut Context<Self>) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use rill_protocol::io::provider::{Description, Path, ProviderProtocol};
 use rill_protocol::io::transport::Direction;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 #[derive(Debug)]
 pub(crate) struct EventEnvelope<T: Flow> {
@@ -19,9 +19,18 @@ pub(crate) struct EventEnvelope<T: Flow> {
 
 pub(crate) enum ControlEvent<T> {
     Flush,
-    AttachCallback { callback: BoxedCallback<T> },
+    AttachCallback {
+        callback: BoxedCallback<T>,
+    },
     // AttachCallbackSender { sender: ActionSender<T> },
     DetachCallback,
+    AttachTicker {
+        interval: Duration,
+        ticker: BoxedTicker<T>,
+    },
+    AttachReactor {
+        reactor: BoxedReactor<T>,
+    },
 }
 
 impl<T: Flow> Action for EventEnvelope<T> {}
@@ -38,6 +47,9 @@ pub type ActionSender<T> = mpsc::UnboundedSender<ActionEnvelope<T>>;
 /// A receiver for actions.
 pub type ActionReceiver<T> = mpsc::UnboundedReceiver<ActionEnvelope<T>>;
 
+pub(crate) type ActivitySender = watch::Sender<bool>;
+pub(crate) type ActivityReceiver = watch::Receiver<bool>;
+
 /// Creates a new control channel.
 pub fn channel<T: Flow>() -> (ActionSender<T>, ActionReceiver<T>) {
     mpsc::unbounded_channel()
@@ -46,6 +58,8 @@ pub fn channel<T: Flow>() -> (ActionSender<T>, ActionReceiver<T>) {
 pub(crate) struct TracerOperator<T: Flow> {
     pub mode: TracerMode<T>,
     pub control_rx: Option<ControlReceiver<T>>,
+    /// Tracks the presence of subscribers of a `Recorder`.
+    pub activity_tx: ActivitySender,
 }
 
 pub(crate) enum TracerMode<T: Flow> {
@@ -98,6 +112,7 @@ impl<T: Flow> Clone for InnerMode<T> {
 pub struct Tracer<T: Flow> {
     description: Arc<Description>,
     control_tx: ControlSender<T>,
+    activity_rx: ActivityReceiver,
     mode: InnerMode<T>,
 }
 
@@ -106,6 +121,7 @@ impl<T: Flow> Clone for Tracer<T> {
         Self {
             description: self.description.clone(),
             control_tx: self.control_tx.clone(),
+            activity_rx: self.activity_rx.clone(),
             mode: self.mode.clone(),
         }
     }
@@ -158,9 +174,11 @@ impl<T: Flow> Tracer<T> {
 
     fn new_inner(path: Path, inner_mode: InnerMode<T>, mode: TracerMode<T>) -> Self {
         let (control_tx, control_rx) = mpsc::unbounded_channel();
+        let (activity_tx, activity_rx) = watch::channel(false);
         let operator = TracerOperator {
             mode,
             control_rx: Some(control_rx),
+            activity_tx,
         };
         let stream_type = T::stream_type();
         let description = Description { path, stream_type };
@@ -169,6 +187,7 @@ impl<T: Flow> Tracer<T> {
         let this = Tracer {
             description: description.clone(),
             control_tx,
+            activity_rx,
             mode: inner_mode,
         };
         if let Err(err) = connector::DISTRIBUTOR.register_tracer(description, operator) {
@@ -190,6 +209,28 @@ impl<T: Flow> Tracer<T> {
         &self.description
     }
 
+    /// Returns `true` if at least one client subscribed to the `Tracer`.
+    ///
+    /// It can be used to skip expensive computations
+    /// if no one watches the data.
+    pub fn is_active(&self) -> bool {
+        *self.activity_rx.borrow()
+    }
+
+    /// Waits until at least one client subscribes to the `Tracer`.
+    ///
+    /// Returns immediately if the `Tracer` is active already.
+    pub async fn wait_active(&self) -> Result<(), Error> {
+        let mut activity_rx = self.activity_rx.clone();
+        while !*activity_rx.borrow() {
+            activity_rx
+                .changed()
+                .await
+                .map_err(|_| Error::msg(format!("Recorder of {} was terminated", self.path())))?;
+        }
+        Ok(())
+    }
+
     /// Send an event to a `Recorder`.
     // TODO: Consider using explicit direction value. What sould Broadcast be?
     pub fn send(&self, event: T::Event, direction: Option<Direction<ProviderProtocol>>) {
@@ -281,8 +322,49 @@ impl<T: Flow> Tracer<T> {
             log::error!("Can't detach the callback from {}: {}", self.path(), err);
         }
     }
+
+    /// Assign a ticker that is called by the `Recorder` periodically
+    /// with the current state. The returned event is applied to the state
+    /// and sent to subscribers.
+    ///
+    /// The ticker lives as long as the `Recorder` of the flow.
+    pub fn ticker<F>(&self, interval: Duration, ticker: F)
+    where
+        F: FnMut(&T) -> Option<T::Event>,
+        F: Send + 'static,
+    {
+        let ticker = Box::new(ticker);
+        let event = ControlEvent::AttachTicker { interval, ticker };
+        if let Err(err) = self.control_tx.send(event) {
+            log::error!("Can't attach the ticker to {}: {}", self.path(), err);
+        }
+    }
+
+    /// Assign a reactor that is called by the `Recorder` for every incoming
+    /// action with the current state. The returned event is applied to the state
+    /// and sent to subscribers before the action is passed to a callback.
+    ///
+    /// The reactor lives as long as the `Recorder` of the flow.
+    pub fn reactor<F>(&self, reactor: F)
+    where
+        F: FnMut(&T, &T::Action) -> Option<T::Event>,
+        F: Send + 'static,
+    {
+        let reactor = Box::new(reactor);
+        let event = ControlEvent::AttachReactor { reactor };
+        if let Err(err) = self.control_tx.send(event) {
+            log::error!("Can't attach the reactor to {}: {}", self.path(), err);
+        }
+    }
 }
 
+/// Boxed ticker.
+pub type BoxedTicker<T> = Box<dyn FnMut(&T) -> Option<<T as Flow>::Event> + Send>;
+
+/// Boxed reactor.
+pub type BoxedReactor<T> =
+    Box<dyn FnMut(&T, &<T as Flow>::Action) -> Option<<T as Flow>::Event> + Send>;
+
 /// The callback that called on flow's incoming actions.
 #[async_trait]
 pub trait ActionCallback<T: Flow>: Send + 'static {
@@ -1,4 +1,4 @@
-use super::state::{TimedAlert, ToastState, ALERTS};
+use super::state::{severity_color, TimedAlert, ToastState, ALERTS};
 use anyhow::Error;
 use rate_ui::shared_object::{DataChanged, SharedObject};
 use rate_ui::widget::{Context, NotificationHandler, Widget, WidgetRuntime};
@@ -89,7 +89,7 @@ impl AlertToastWidget {
           <div class="toast show">
             <div class="toast-header">
               //<img src="..." class="rounded me-2" alt="..." />
-              <strong class="me-auto">{ &item.origin }</strong>
+              <strong class=format!("me-auto text-{}", severity_color(item.severity))>{ &item.origin }</strong>
               <small>{ ago }</small>
               <button type="button" class="btn-close"
                 onclick=ctx.event(Msg::Hide(idx))
@@ -1,5 +1,6 @@
 use rate_ui::shared_object::{RouterState, SharedObject};
 use rate_ui::storage::typed_storage::Storable;
+use rrpack_prime::transparent::alert::Severity;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -11,15 +12,18 @@ thread_local! {
 pub struct TimedAlert {
     pub origin: String,
     pub ms: u64,
+    #[serde(default)]
+    pub severity: Severity,
     pub message: String,
 }
 
 impl TimedAlert {
-    pub fn new(origin: String, message: String) -> Self {
+    pub fn new(origin: String, severity: Severity, message: String) -> Self {
         let ms = js_sys::Date::now();
         Self {
             origin,
             ms: ms as u64,
+            severity,
             message,
         }
     }
@@ -29,11 +33,35 @@ impl TimedAlert {
     }
 }
 
+/// A bootstrap color of the severity.
+pub fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warn => "warning",
+        Severity::Critical => "danger",
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct ToastState {
     pub alerts: VecDeque<TimedAlert>,
 }
 
+impl ToastState {
+    /// Adds an alert or renews the same one to avoid duplicates.
+    pub fn push(&mut self, alert: TimedAlert) {
+        let same = self
+            .alerts
+            .iter_mut()
+            .find(|item| item.origin == alert.origin && item.message == alert.message);
+        if let Some(item) = same {
+            item.renew();
+        } else {
+            self.alerts.push_back(alert);
+        }
+    }
+}
+
 impl Storable for ToastState {
     fn key() -> &'static str {
         module_path!()
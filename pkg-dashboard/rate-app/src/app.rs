@@ -87,6 +87,7 @@ impl Widget for AppWidget {
                 </nav>
                 <alerts::AlertToast />
                 <cases::Loader />
+                <cases::IssuesLoader />
                 <explorer::Loader />
             </div>
         }
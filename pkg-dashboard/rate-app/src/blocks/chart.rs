@@ -84,6 +84,7 @@ impl<T: ChartSpec> OnBridgeEvent<GraphicsAgent> for BasicChartWidget<T> {
                         self.spec.x_formatter(),
                         self.spec.y_formatter(),
                         self.spec.data(),
+                        self.spec.colors(),
                     )?;
                 }
             }
@@ -103,6 +104,11 @@ pub trait ChartSpec: Default + 'static {
 
     fn data(&self) -> &Vec<Vec<(i64, f32)>>;
 
+    /// Colors of lines. Colors picked automatically if not set.
+    fn colors(&self) -> &[Option<String>] {
+        &[]
+    }
+
     fn y_min(&self) -> f32 {
         0.0
     }
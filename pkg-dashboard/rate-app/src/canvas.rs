@@ -4,6 +4,7 @@ use derive_more::{Deref, DerefMut};
 // TODO: Remove it or use
 use plotters::prelude::*;
 use plotters_canvas::CanvasBackend;
+use rate_ui::packages::masterpiece::palette;
 use rate_ui::packages::or_fail::{Fail, Fasten};
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d as Context2d, HtmlCanvasElement};
@@ -126,15 +127,14 @@ impl DrawCanvas {
     pub fn draw_charts(
         &mut self,
         secs: i64,
-        mut from_color: usize,
+        from_color: usize,
         min: f32,
         max: f32,
         x_formatter: &dyn Fn(&i64) -> String,
         y_formatter: &dyn Fn(&f32) -> String,
         data: &[Vec<(i64, f32)>],
+        colors: &[Option<String>],
     ) -> Result<(), Error> {
-        from_color += 5;
-
         let canvas = self.canvas.canvas()?.clone();
 
         let root_area = CanvasBackend::with_canvas_object(canvas)
@@ -161,11 +161,19 @@ impl DrawCanvas {
         for (col, line) in data.iter().enumerate() {
             let area_color;
             let line_color;
-            if single {
+            let custom_color = colors
+                .get(col)
+                .and_then(Option::as_deref)
+                .and_then(parse_color);
+            if let Some(color) = custom_color {
+                line_color = color.to_rgba();
+                area_color = line_color.mix(0.2).to_rgba();
+            } else if single {
                 area_color = RGBColor(0xD2, 0x09, 0x09).mix(0.2).to_rgba();
                 line_color = RGBColor(0x42, 0x11, 0xCC).mix(1.0).to_rgba();
             } else {
-                line_color = Palette99::pick(from_color + col).to_rgba();
+                let color = parse_color(palette::color(from_color + col)).unwrap_or(BLACK);
+                line_color = color.to_rgba();
                 area_color = line_color.mix(0.2).to_rgba();
             }
             let line = line.iter().cloned();
@@ -175,6 +183,149 @@ impl DrawCanvas {
 
         Ok(())
     }
+
+    /// Draws candles as `(x, open, high, low, close)` tuples.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_candles(
+        &mut self,
+        secs: i64,
+        interval_ms: i64,
+        min: f32,
+        max: f32,
+        x_formatter: &dyn Fn(&i64) -> String,
+        y_formatter: &dyn Fn(&f32) -> String,
+        candles: &[(i64, f32, f32, f32, f32)],
+    ) -> Result<(), Error> {
+        let canvas = self.canvas.canvas()?.clone();
+
+        let root_area = CanvasBackend::with_canvas_object(canvas)
+            .ok_or_else(|| Error::msg("no canvas backend created"))?
+            .into_drawing_area();
+
+        let mut ctx = ChartBuilder::on(&root_area)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .margin(60)
+            .build_cartesian_2d((-secs * 1_000)..interval_ms, min..max)?;
+
+        ctx.configure_mesh()
+            .light_line_style(&RGBColor(0xF8, 0xF9, 0xFA))
+            .label_style(("Jost", 26))
+            .x_label_formatter(x_formatter)
+            .y_label_formatter(y_formatter)
+            .draw()?;
+
+        let (pixels, _) = ctx.plotting_area().dim_in_pixel();
+        let slots = (secs * 1_000 / interval_ms.max(1)).max(1);
+        let width = (pixels as i64 * 7 / 10 / slots).max(1) as u32;
+        let gain = RGBColor(0x19, 0x87, 0x54);
+        let loss = RGBColor(0xDC, 0x35, 0x45);
+        let series = candles.iter().map(|(x, open, high, low, close)| {
+            CandleStick::new(
+                *x,
+                *open,
+                *high,
+                *low,
+                *close,
+                gain.filled(),
+                loss.filled(),
+                width,
+            )
+        });
+        ctx.draw_series(series)?;
+
+        Ok(())
+    }
+
+    /// Draws cumulative volumes of bids and asks by prices.
+    pub fn draw_depth(
+        &mut self,
+        min: f32,
+        max: f32,
+        volume: f32,
+        bids: &[(f32, f32)],
+        asks: &[(f32, f32)],
+    ) -> Result<(), Error> {
+        let canvas = self.canvas.canvas()?.clone();
+
+        let root_area = CanvasBackend::with_canvas_object(canvas)
+            .ok_or_else(|| Error::msg("no canvas backend created"))?
+            .into_drawing_area();
+
+        let mut ctx = ChartBuilder::on(&root_area)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .margin(60)
+            .build_cartesian_2d(min..max, 0.0..volume)?;
+
+        ctx.configure_mesh()
+            .light_line_style(&RGBColor(0xF8, 0xF9, 0xFA))
+            .label_style(("Jost", 26))
+            .draw()?;
+
+        let sides = [
+            (bids, RGBColor(0x19, 0x87, 0x54)),
+            (asks, RGBColor(0xDC, 0x35, 0x45)),
+        ];
+        for (levels, color) in sides {
+            let line = levels.iter().cloned();
+            let area_color = color.mix(0.2).to_rgba();
+            let series = AreaSeries::new(line, 0.0, &area_color).border_style(&color);
+            ctx.draw_series(series)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws cells as `(col, row, scale)` tuples where `scale` is in range `[0;1]`.
+    pub fn draw_heatmap(
+        &mut self,
+        rows: &[String],
+        cols: &[String],
+        cells: &[(usize, usize, f64)],
+    ) -> Result<(), Error> {
+        let canvas = self.canvas.canvas()?.clone();
+
+        let root_area = CanvasBackend::with_canvas_object(canvas)
+            .ok_or_else(|| Error::msg("no canvas backend created"))?
+            .into_drawing_area();
+
+        let x_formatter = |idx: &usize| cols.get(*idx).cloned().unwrap_or_default();
+        let y_formatter = |idx: &usize| rows.get(*idx).cloned().unwrap_or_default();
+        let mut ctx = ChartBuilder::on(&root_area)
+            .set_label_area_size(LabelAreaPosition::Left, 120)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .margin(60)
+            .build_cartesian_2d(0..cols.len(), 0..rows.len())?;
+
+        ctx.configure_mesh()
+            .disable_mesh()
+            .label_style(("Jost", 26))
+            .x_labels(cols.len())
+            .y_labels(rows.len())
+            .x_label_formatter(&x_formatter)
+            .y_label_formatter(&y_formatter)
+            .draw()?;
+
+        let series = cells.iter().map(|(col, row, scale)| {
+            // From blue for low values to red for high ones
+            let color = HSLColor((1.0 - scale) * 240.0 / 360.0, 0.7, 0.5);
+            Rectangle::new([(*col, *row), (col + 1, row + 1)], color.filled())
+        });
+        ctx.draw_series(series)?;
+
+        Ok(())
+    }
+}
+
+/// Parses a color in the `#RRGGBB` format.
+fn parse_color(color: &str) -> Option<RGBColor> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |idx: usize| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok();
+    Some(RGBColor(channel(0)?, channel(2)?, channel(4)?))
 }
 
 pub fn sustain<Y: Copy>(mut iter: impl Iterator<Item = (i64, Y)>, last_x: i64) -> Vec<(i64, Y)> {
@@ -1,3 +1,4 @@
 pub mod render;
 
 mod prime;
+mod trade;
@@ -1,9 +1,14 @@
-use crate::alerts::state::{TimedAlert, ALERTS};
+use crate::alerts::state::{severity_color, TimedAlert, ALERTS};
 use rate_ui::shared_object::SharedObject;
 use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
 use rate_ui::widget::{Context, Widget, WidgetRuntime};
 use rill_protocol::io::provider::Path;
-use rrpack_prime::transparent::alert::{AlertEvent, AlertState};
+use rrpack_basis::frames::TimedEvent;
+use rrpack_prime::transparent::alert::{
+    AlertAction, AlertEvent, AlertRecord, AlertState, RecordKind, Severity,
+};
+use std::time::Duration;
+use timeago::Formatter;
 use yew::{classes, html, Classes, Html};
 
 pub type AlertCard = WidgetRuntime<AlertCardWidget>;
@@ -12,11 +17,15 @@ pub type AlertCard = WidgetRuntime<AlertCardWidget>;
 pub struct AlertCardWidget {
     // TODO: Add ignored count
     silent: bool,
+    show_log: bool,
+    formatter: Formatter,
 }
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     Toggle,
+    ToggleLog,
+    Ack(Option<u64>),
 }
 
 impl Widget for AlertCardWidget {
@@ -40,6 +49,13 @@ impl Widget for AlertCardWidget {
                 self.silent = !self.silent;
                 ctx.redraw();
             }
+            Msg::ToggleLog => {
+                self.show_log = !self.show_log;
+                ctx.redraw();
+            }
+            Msg::Ack(id) => {
+                ctx.do_action(AlertAction::Ack { id });
+            }
         }
     }
 
@@ -56,18 +72,95 @@ impl Widget for AlertCardWidget {
         } else {
             (Some("active"), "bi-bell-fill")
         };
+        let state = ctx.meta().state();
+        let firing = state
+            .filter(|state| !state.firing.is_empty())
+            .map(|state| {
+                let color = state.severity().map(severity_color).unwrap_or_default();
+                html! {
+                    <span class=format!("badge ms-1 bg-{}", color)>{ state.firing.len() }</span>
+                }
+            })
+            .unwrap_or_default();
+        let unacked = state
+            .map(|state| state.unacked().count())
+            .unwrap_or_default();
         let body = {
             html! {
-                <button type="button" class=classes!(Classes::from("btn btn-sm"), color, silent)
-                    onclick=ctx.event(Msg::Toggle)
-                >
-                    { "Alerts: " }<i class=bell></i>
-                </button>
+                <div class="btn-group">
+                    <button type="button" class=classes!(Classes::from("btn btn-sm"), color, silent)
+                        onclick=ctx.event(Msg::Toggle)
+                    >
+                        { "Alerts: " }<i class=bell></i>{ firing }
+                    </button>
+                    <button type="button" class="btn btn-sm btn-outline-secondary"
+                        onclick=ctx.event(Msg::ToggleLog)
+                    >
+                        { "Log: " }{ unacked }
+                    </button>
+                </div>
             }
         };
+        let log = state
+            .filter(|_| self.show_log)
+            .map(|state| self.render_log(state, ctx))
+            .unwrap_or_default();
         html! {
             <div yew=module_path!() class="text-center pt-3">
                 { body }
+                { log }
+            </div>
+        }
+    }
+}
+
+impl AlertCardWidget {
+    fn render_log(&self, state: &AlertState, ctx: &Context<Self>) -> Html {
+        let now = Duration::from_millis(js_sys::Date::now() as u64);
+        html! {
+            <div class="text-start small mt-2">
+                <div class="d-flex flex-row justify-content-end">
+                    <button type="button" class="btn btn-sm btn-link"
+                        onclick=ctx.event(Msg::Ack(None))
+                    >{ "Acknowledge all" }</button>
+                </div>
+                { for state.history.iter().rev().map(|record| self.render_record(record, now, ctx)) }
+            </div>
+        }
+    }
+
+    fn render_record(
+        &self,
+        record: &TimedEvent<AlertRecord>,
+        now: Duration,
+        ctx: &Context<Self>,
+    ) -> Html {
+        let ts = Duration::from_millis(record.timestamp.0 as u64);
+        let ago = self.formatter.convert(now.saturating_sub(ts));
+        let item = &record.event;
+        let kind = match item.kind {
+            RecordKind::Notify => "",
+            RecordKind::Firing => "Firing: ",
+            RecordKind::Resolved => "Resolved: ",
+        };
+        let ack = if item.acked {
+            html! { <i class="bi-check2 text-muted"></i> }
+        } else {
+            html! {
+                <button type="button" class="btn btn-sm btn-link p-0"
+                    onclick=ctx.event(Msg::Ack(Some(item.id)))
+                >{ "Ack" }</button>
+            }
+        };
+        let muted = if item.acked { Some("text-muted") } else { None };
+        html! {
+            <div class=classes!("d-flex", "flex-row", "align-items-center", "py-1", muted)>
+                <span class=format!("badge me-2 bg-{}", severity_color(item.severity))>
+                    { item.severity.to_string() }
+                </span>
+                <div class="flex-grow-1">{ kind }{ &item.text }</div>
+                <div class="px-2 text-muted text-nowrap">{ ago }</div>
+                { ack }
             </div>
         }
     }
@@ -83,20 +176,25 @@ impl WiredWidget<SingleFlowMeta<Self>> for AlertCardWidget {
     fn state_update(
         &mut self,
         tag: &Path,
-        event: &AlertEvent,
+        event: &TimedEvent<AlertEvent>,
         _reloaded: &mut bool,
         _ctx: &mut Context<Self>,
     ) {
-        match event {
-            AlertEvent::Notify { text } => {
-                if !self.silent {
-                    let alerts = ALERTS.with(SharedObject::clone);
-                    let mut state = alerts.write();
-                    let origin = tag.iter().last().cloned().unwrap_or_default();
-                    let alert = TimedAlert::new(origin.into(), text.clone());
-                    state.alerts.push_back(alert);
-                }
-            }
+        if self.silent {
+            return;
         }
+        let (severity, text) = match &event.event {
+            AlertEvent::Notify { severity, text } => (*severity, text.clone()),
+            AlertEvent::Firing { severity, text, .. } => (*severity, format!("Firing: {}", text)),
+            AlertEvent::Resolved { text, .. } => (Severity::Info, format!("Resolved: {}", text)),
+            AlertEvent::Ack { .. } => {
+                return;
+            }
+        };
+        let alerts = ALERTS.with(SharedObject::clone);
+        let mut state = alerts.write();
+        let origin = tag.iter().last().cloned().unwrap_or_default();
+        let alert = TimedAlert::new(origin.into(), severity, text);
+        state.push(alert);
     }
 }
@@ -0,0 +1,105 @@
+use crate::canvas::DrawCanvas;
+use anyhow::Error;
+use rate_ui::agents::graphics::{GraphicsAgent, GraphicsResponse};
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, OnBridgeEvent, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_prime::visual::heatmap::HeatmapState;
+use yew::{html, Html};
+
+pub type HeatmapCard = WidgetRuntime<HeatmapCardWidget>;
+
+#[derive(Default)]
+pub struct HeatmapCardWidget {
+    canvas: DrawCanvas,
+}
+
+impl Widget for HeatmapCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        ctx.graphics().on_frame(true);
+        ctx.graphics().track_size(self.canvas.node_ref().clone());
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn rendered(&mut self, first: bool) -> Result<(), Error> {
+        if first {
+            self.canvas.bind()?;
+            self.canvas.resize()?;
+        }
+        Ok(())
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let scale = {
+            if let Some(state) = ctx.meta().state() {
+                let bounds = state.bounds();
+                let label = &state.spec.label;
+                html! {
+                    <div class="d-flex flex-row justify-content-center px-3">
+                        <div class="px-2 text-primary">{ bounds.min / label.divisor } { &label.caption }</div>
+                        <div class="px-2">{ "—" }</div>
+                        <div class="px-2 text-danger">{ bounds.max / label.divisor } { &label.caption }</div>
+                    </div>
+                }
+            } else {
+                Html::default()
+            }
+        };
+        let node_ref = self.canvas.node_ref().clone();
+        html! {
+            // IMPORTANT! It change it check the chart no continuously grow in height.
+            <div yew=module_path!() style="display: block;">
+                { scale }
+                <canvas
+                    ref=node_ref
+                    style="width: 100%; height: 100%; padding: 0; margin: 0; box-sizing: border-box;"
+                />
+            </div>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for HeatmapCardWidget {
+    type Flow = HeatmapState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
+
+impl OnBridgeEvent<GraphicsAgent> for HeatmapCardWidget {
+    fn on_event(&mut self, event: GraphicsResponse, ctx: &mut Context<Self>) -> Result<(), Error> {
+        match event {
+            GraphicsResponse::SizeChanged(_) => {
+                self.canvas.resize()?;
+            }
+            GraphicsResponse::Frame => {
+                if let Some(state) = ctx.meta().state() {
+                    self.canvas.resize()?;
+                    self.canvas.clear()?;
+                    let bounds = state.bounds();
+                    let mut cells = Vec::new();
+                    for (y, row) in state.rows.iter().enumerate() {
+                        for (x, col) in state.cols.iter().enumerate() {
+                            if let Some(value) = state.get(row, col) {
+                                cells.push((x, y, bounds.scale(value)));
+                            }
+                        }
+                    }
+                    self.canvas.draw_heatmap(&state.rows, &state.cols, &cells)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
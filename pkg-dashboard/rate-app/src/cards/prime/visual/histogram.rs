@@ -32,6 +32,8 @@ impl Widget for HistogramCardWidget {
                 html! {
                     <div class="d-flex flex-column w-100 py-3 px-4 overflow-auto">
                         { for state.buckets.iter().map(|(key, value)| self.render_item(key, value)) }
+                        <hr/>
+                        { for state.quantiles().map(|(q, value)| self.render_quantile(q, value)) }
                     </div>
                 }
             } else {
@@ -55,6 +57,18 @@ impl HistogramCardWidget {
             </div>
         }
     }
+
+    fn render_quantile(&self, q: f64, value: Option<f64>) -> Html {
+        let value = value
+            .map(|value| format!("{:.2}", value))
+            .unwrap_or_else(|| "-".into());
+        html! {
+            <div class="d-flex flex-row justify-content-between">
+                <div class="fw-bold">{ format!("p{}", q * 100.0) }</div>
+                <div>{ value }</div>
+            </div>
+        }
+    }
 }
 
 impl WiredWidget<SingleFlowMeta<Self>> for HistogramCardWidget {
@@ -2,16 +2,33 @@ use crate::blocks;
 use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
 use rate_ui::widget::{Context, Widget, WidgetRuntime};
 use rill_protocol::io::provider::Path;
-use rrpack_prime::visual::live_tail::{LiveTailState, LogRecord};
-use yew::{html, Html};
+use rrpack_basis::frames::TimedEvent;
+use rrpack_prime::visual::live_tail::{
+    level_name, LiveTailAction, LiveTailState, LogFilter, LogRecord, LEVELS,
+};
+use yew::{html, ChangeData, Html, InputData};
+use yew_components::Select;
+
+const ALL_LEVELS: &str = "ALL";
 
 pub type LiveTailCard = WidgetRuntime<LiveTailCardWidget>;
 
 #[derive(Default)]
-pub struct LiveTailCardWidget {}
+pub struct LiveTailCardWidget {
+    search: String,
+    /// Records frozen by the pause.
+    paused: Option<Vec<TimedEvent<LogRecord>>>,
+}
+
+pub enum Msg {
+    SetLevel(String),
+    SetModule(String),
+    Search(String),
+    TogglePause,
+}
 
 impl Widget for LiveTailCardWidget {
-    type Event = ();
+    type Event = Msg;
     type Tag = Option<Path>;
     type Properties = SingleFlowProps;
     type Meta = SingleFlowMeta<Self>;
@@ -25,25 +42,60 @@ impl Widget for LiveTailCardWidget {
         ctx.rewire(path);
     }
 
+    fn on_event(&mut self, event: Self::Event, ctx: &mut Context<Self>) {
+        match event {
+            Msg::SetLevel(level) => {
+                if let Some(mut filter) = self.filter(ctx) {
+                    filter.min_level = Some(level).filter(|level| level != ALL_LEVELS);
+                    ctx.do_action(LiveTailAction::SetFilter(filter));
+                }
+            }
+            Msg::SetModule(module) => {
+                if let Some(mut filter) = self.filter(ctx) {
+                    filter.module = Some(module).filter(|module| !module.is_empty());
+                    ctx.do_action(LiveTailAction::SetFilter(filter));
+                }
+            }
+            Msg::Search(search) => {
+                self.search = search;
+                ctx.redraw();
+            }
+            Msg::TogglePause => {
+                if self.paused.take().is_none() {
+                    self.paused = ctx
+                        .meta()
+                        .state()
+                        .map(|state| state.records().cloned().collect());
+                }
+                ctx.redraw();
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let body = {
             if let Some(state) = ctx.meta().state() {
+                let records: Vec<&TimedEvent<LogRecord>> = match self.paused.as_ref() {
+                    Some(records) => records.iter().collect(),
+                    None => state.records().collect(),
+                };
                 html! {
-                    //<div class="d-flex flex-column w-100 py-3 px-4 overflow-auto">
-                    <table class="table">
-                        <thead>
-                            <tr>
-                                <th width="10%">{ "Module" }</th>
-                                <th width="6%">{ "Level" }</th>
-                                <th width="14%">{ "Timestamp" }</th>
-                                <th width="70%">{ "Text" }</th>
-                            </tr>
-                        </thead>
-                        <tbody>
-                            { for state.frame.iter().rev().map(|record| self.render_record(record)) }
-                        </tbody>
-                    </table>
-                    //</div>
+                    <>
+                        { self.render_toolbar(state, ctx) }
+                        <table class="table">
+                            <thead>
+                                <tr>
+                                    <th width="10%">{ "Module" }</th>
+                                    <th width="6%">{ "Level" }</th>
+                                    <th width="14%">{ "Timestamp" }</th>
+                                    <th width="70%">{ "Text" }</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for records.into_iter().rev().map(|record| self.render_record(record)) }
+                            </tbody>
+                        </table>
+                    </>
                 }
             } else {
                 blocks::spinner("Connecting...")
@@ -58,16 +110,94 @@ impl Widget for LiveTailCardWidget {
 }
 
 impl LiveTailCardWidget {
-    fn render_record(&self, record: &LogRecord) -> Html {
+    fn filter(&self, ctx: &Context<Self>) -> Option<LogFilter> {
+        ctx.meta().state().map(|state| state.filter.clone())
+    }
+
+    fn render_toolbar(&self, state: &LiveTailState, ctx: &Context<Self>) -> Html {
+        let levels: Vec<String> = std::iter::once(ALL_LEVELS)
+            .chain(LEVELS.iter().copied())
+            .map(String::from)
+            .collect();
+        let level = state
+            .filter
+            .min_level
+            .clone()
+            .unwrap_or_else(|| ALL_LEVELS.into());
+        let module = state.filter.module.clone().unwrap_or_default();
+        let (pause, pause_icon) = if self.paused.is_some() {
+            ("btn-warning", "bi-play-fill")
+        } else {
+            ("btn-outline-secondary", "bi-pause-fill")
+        };
         html! {
-            <tr>
+            <div class="d-flex flex-row pt-2 pb-1">
+                <Select<String>
+                    class="form-select form-select-sm w-auto me-2"
+                    options=levels
+                    selected=Some(level)
+                    on_change=ctx.callback(Msg::SetLevel)
+                />
+                <input class="form-control form-control-sm w-auto me-2"
+                    placeholder="Module"
+                    value=module
+                    onchange=ctx.callback(|data: ChangeData| {
+                        match data {
+                            ChangeData::Value(value) => Msg::SetModule(value),
+                            _ => Msg::SetModule(String::new()),
+                        }
+                    })
+                    />
+                <input class="form-control form-control-sm me-2"
+                    placeholder="Search"
+                    value=self.search.clone()
+                    oninput=ctx.callback(|data: InputData| Msg::Search(data.value))
+                    />
+                <button type="button" class=format!("btn btn-sm {}", pause)
+                    onclick=ctx.event(Msg::TogglePause)
+                >
+                    <i class=pause_icon></i>
+                </button>
+            </div>
+        }
+    }
+
+    fn render_record(&self, record: &TimedEvent<LogRecord>) -> Html {
+        let record = &record.event;
+        let style = match level_name(&record.level) {
+            Some("ERROR") => "text-danger",
+            Some("WARN") => "text-warning",
+            Some("TRACE") | Some("DEBUG") => "text-muted",
+            _ => "",
+        };
+        html! {
+            <tr class=style>
                 <td>{ &record.module }</td>
                 <td>{ &record.level }</td>
                 <td>{ &record.timestamp }</td>
-                <td>{ &record.content }</td>
+                <td>{ self.highlight(&record.content) }</td>
             </tr>
         }
     }
+
+    /// Wraps occurrences of the search text (case-insensitive) with marks.
+    fn highlight(&self, text: &str) -> Html {
+        if self.search.is_empty() {
+            return html! { { text } };
+        }
+        let haystack = text.to_ascii_lowercase();
+        let needle = self.search.to_ascii_lowercase();
+        let mut parts = Vec::new();
+        let mut from = 0;
+        for (idx, _) in haystack.match_indices(&needle) {
+            let to = idx + needle.len();
+            parts.push(html! { { &text[from..idx] } });
+            parts.push(html! { <mark>{ &text[idx..to] }</mark> });
+            from = to;
+        }
+        parts.push(html! { { &text[from..] } });
+        html! { <>{ for parts }</> }
+    }
 }
 
 impl WiredWidget<SingleFlowMeta<Self>> for LiveTailCardWidget {
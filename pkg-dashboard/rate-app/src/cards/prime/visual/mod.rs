@@ -7,6 +7,9 @@ pub use counter::CounterCard;
 mod gauge;
 pub use gauge::GaugeCard;
 
+mod heatmap;
+pub use heatmap::HeatmapCard;
+
 mod histogram;
 pub use histogram::HistogramCard;
 
@@ -16,8 +19,17 @@ pub use live_text::LiveTextCard;
 mod live_tail;
 pub use live_tail::LiveTailCard;
 
+mod multi_pulse;
+pub use multi_pulse::MultiPulseCard;
+
 mod pulse;
 pub use pulse::PulseCard;
 
+mod sketch;
+pub use sketch::SketchCard;
+
+mod status;
+pub use status::StatusCard;
+
 mod table;
 pub use table::TableCard;
@@ -0,0 +1,172 @@
+use crate::blocks;
+use crate::blocks::chart::{ChartSpec, Formatter};
+use crate::canvas;
+use rate_ui::packages::masterpiece::palette;
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_prime::visual::multi_pulse::MultiPulseState;
+use yew::{html, Html};
+
+pub type MultiPulseCard = WidgetRuntime<MultiPulseCardWidget>;
+
+#[derive(Default)]
+pub struct MultiPulseCardWidget {}
+
+impl Widget for MultiPulseCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let path = ctx.properties().path.clone().of_server();
+        let legend = {
+            if let Some(state) = ctx.meta().state() {
+                html! {
+                    <div class="d-flex flex-row flex-wrap justify-content-center px-3">
+                        { for state.ordered_series().into_iter().enumerate().map(|(idx, (name, _))| self.render_series(state, idx, name)) }
+                    </div>
+                }
+            } else {
+                Html::default()
+            }
+        };
+        html! {
+            // IMPORTANT! It change it check the pulse no continuously grow in height.
+            <div yew=module_path!() style="display: block;">
+                { legend }
+                <blocks::BasicChart<MultiPulseSpec> path=path />
+            </div>
+        }
+    }
+}
+
+impl MultiPulseCardWidget {
+    fn render_series(&self, state: &MultiPulseState, idx: usize, name: &str) -> Html {
+        let series = state.spec.series(name);
+        let caption = series.map(|series| series.caption()).unwrap_or(name);
+        let style = format!("color: {};", series_color(state, idx, name));
+        html! {
+            <div class="px-2 fw-bold" style=style>{ caption }</div>
+        }
+    }
+}
+
+/// The same color is used by the legend and by the line of the series.
+fn series_color<'a>(state: &'a MultiPulseState, idx: usize, name: &str) -> &'a str {
+    state
+        .spec
+        .series(name)
+        .and_then(|series| series.color.as_deref())
+        .unwrap_or_else(|| palette::color(idx))
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for MultiPulseCardWidget {
+    type Flow = MultiPulseState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
+
+#[derive(Default)]
+struct MultiPulseSpec {
+    retain: i64,
+    min: f32,
+    max: f32,
+    formatter: Option<Box<Formatter<f32>>>,
+    lines: Vec<Vec<(i64, f32)>>,
+    colors: Vec<Option<String>>,
+}
+
+impl ChartSpec for MultiPulseSpec {
+    type Flow = MultiPulseState;
+
+    fn upgrade(&mut self, state: &Self::Flow) {
+        if self.formatter.is_none() {
+            let label = state.spec.label.clone();
+            let func =
+                move |value: &f32| format!("{} {}", value / label.divisor as f32, label.caption);
+            self.formatter = Some(Box::new(func));
+        }
+        self.retain = state.spec.retain as i64;
+        self.lines.clear();
+        self.colors.clear();
+        let mut min = f64::MAX;
+        let mut max = f64::MIN;
+        for item in state.frames.values().flat_map(|frame| frame.iter()) {
+            let value = item.event;
+            if value < min {
+                min = value;
+            }
+            if value > max {
+                max = value;
+            }
+        }
+        if min <= max {
+            self.min = state.spec.range.min.min(min) as f32;
+            self.max = state.spec.range.max.max(max) as f32;
+            // TODO: Avoid using time here!!!
+            let x_to = js_sys::Date::now() as i64;
+            for (idx, (name, frame)) in state.ordered_series().into_iter().enumerate() {
+                let line = canvas::sustain(
+                    frame.iter().map(|timed_event| {
+                        (
+                            timed_event.timestamp.0 as i64 - x_to,
+                            timed_event.event as f32,
+                        )
+                    }),
+                    0,
+                );
+                self.lines.push(line);
+                let color = series_color(state, idx, name).to_string();
+                self.colors.push(Some(color));
+            }
+        } else {
+            self.min = 0.0;
+            self.max = 0.0;
+        }
+    }
+
+    fn data(&self) -> &Vec<Vec<(i64, f32)>> {
+        &self.lines
+    }
+
+    fn colors(&self) -> &[Option<String>] {
+        &self.colors
+    }
+
+    fn secs(&self) -> i64 {
+        self.retain
+    }
+
+    fn y_min(&self) -> f32 {
+        self.min
+    }
+
+    fn y_max(&self) -> f32 {
+        self.max
+    }
+
+    fn x_formatter(&self) -> &Formatter<i64> {
+        &canvas::formatter_sec
+    }
+
+    fn y_formatter(&self) -> &Formatter<f32> {
+        if let Some(f) = self.formatter.as_ref() {
+            f
+        } else {
+            &canvas::formatter_plain
+        }
+    }
+}
@@ -69,11 +69,10 @@ impl ChartSpec for PulseSpec {
             self.formatter = Some(Box::new(func));
         }
         self.retain = state.spec.retain as i64;
-        if state.frame.len() > 0 {
+        if !state.is_empty() {
             let mut min = f64::MAX;
             let mut max = f64::MIN;
-            for item in state.frame.iter() {
-                let value = item.event;
+            for (_, value) in state.points() {
                 if value < min {
                     min = value;
                 }
@@ -87,12 +86,9 @@ impl ChartSpec for PulseSpec {
             let x_to = js_sys::Date::now() as i64;
             self.lines.clear();
             let usage = canvas::sustain(
-                state.frame.iter().map(|timed_event| {
-                    (
-                        timed_event.timestamp.0 as i64 - x_to,
-                        timed_event.event as f32,
-                    )
-                }),
+                state
+                    .points()
+                    .map(|(timestamp, value)| (timestamp.0 as i64 - x_to, value as f32)),
                 0,
             );
             self.lines.push(usage);
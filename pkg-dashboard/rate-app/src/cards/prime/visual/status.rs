@@ -0,0 +1,139 @@
+use crate::blocks;
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_prime::visual::status::{Indicator, Level, StatusState};
+use std::time::Duration;
+use timeago::Formatter;
+use yew::{html, Html};
+
+/// How often to refresh the time in state.
+const REFRESH_INTERVAL: u64 = 1_000;
+
+pub type StatusCard = WidgetRuntime<StatusCardWidget>;
+
+pub struct StatusCardWidget {
+    formatter: Formatter,
+}
+
+impl Default for StatusCardWidget {
+    fn default() -> Self {
+        let mut formatter = Formatter::new();
+        formatter.ago("");
+        Self { formatter }
+    }
+}
+
+#[derive(Clone)]
+pub enum Msg {
+    TimerRefresh,
+}
+
+impl Widget for StatusCardWidget {
+    type Event = Msg;
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        ctx.schedule(REFRESH_INTERVAL, Msg::TimerRefresh);
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn on_event(&mut self, event: Self::Event, ctx: &mut Context<Self>) {
+        match event {
+            Msg::TimerRefresh => {
+                ctx.schedule(REFRESH_INTERVAL, Msg::TimerRefresh);
+                ctx.redraw();
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let body = {
+            if let Some(state) = ctx.meta().state() {
+                let now = Duration::from_millis(js_sys::Date::now() as u64);
+                let history = state
+                    .history
+                    .iter()
+                    .flat_map(|history| history.iter().rev());
+                html! {
+                    <div class="d-flex flex-column w-100 py-3 px-4 overflow-auto">
+                        <div class="d-flex flex-row align-items-center">
+                            { self.render_light(state.level(), "2rem") }
+                            <div class="px-3 fw-bold">
+                                { state.worst().map(|indicator| self.render_time(indicator, now)).unwrap_or_default() }
+                            </div>
+                            <div class="text-muted">
+                                { state.indicator.as_ref().map(|indicator| indicator.message.as_str()).unwrap_or_default() }
+                            </div>
+                        </div>
+                        { for state.children.iter().map(|(name, indicator)| self.render_child(name, indicator, now)) }
+                        <hr/>
+                        { for history.map(|record| {
+                            let report = &record.event;
+                            html! {
+                                <div class="d-flex flex-row align-items-center small">
+                                    { self.render_light(Some(report.level), "0.6rem") }
+                                    <div class="px-2">{ report.child.as_deref().unwrap_or_default() }</div>
+                                    <div class="text-muted">{ &report.message }</div>
+                                </div>
+                            }
+                        }) }
+                    </div>
+                }
+            } else {
+                blocks::spinner("Connecting...")
+            }
+        };
+        html! {
+            <div yew=module_path!() class="overflow-auto" style="height: 100%; width: 100%;">
+                { body }
+            </div>
+        }
+    }
+}
+
+impl StatusCardWidget {
+    fn render_light(&self, level: Option<Level>, size: &str) -> Html {
+        let color = match level {
+            Some(Level::Ok) => "bg-success",
+            Some(Level::Degraded) => "bg-warning",
+            Some(Level::Down) => "bg-danger",
+            None => "bg-secondary",
+        };
+        let style = format!("width: {0}; height: {0};", size);
+        html! {
+            <div class=format!("rounded-circle flex-shrink-0 {}", color) style=style></div>
+        }
+    }
+
+    fn render_time(&self, indicator: &Indicator, now: Duration) -> String {
+        let changed = Duration::from_millis(indicator.changed.0 as u64);
+        self.formatter.convert(now.saturating_sub(changed))
+    }
+
+    fn render_child(&self, name: &str, indicator: &Indicator, now: Duration) -> Html {
+        html! {
+            <div class="d-flex flex-row align-items-center mt-2">
+                { self.render_light(Some(indicator.level), "1rem") }
+                <div class="px-3 fw-bold">{ name }</div>
+                <div class="px-2">{ self.render_time(indicator, now) }</div>
+                <div class="text-muted">{ &indicator.message }</div>
+            </div>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for StatusCardWidget {
+    type Flow = StatusState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
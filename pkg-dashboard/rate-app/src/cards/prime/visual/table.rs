@@ -1,24 +1,34 @@
 use crate::blocks;
+use ordered_float::OrderedFloat;
 use rate_ui::utils;
 use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
 use rate_ui::widget::{Context, Widget, WidgetRuntime};
 use rill_protocol::flow::core::Flow;
 use rill_protocol::io::provider::Path;
-use rrpack_prime::visual::table::{Col, ColRecord, Row, RowRecord, TableEvent, TableState};
+use rrpack_prime::visual::table::{
+    Cell, CellStyle, Col, ColKind, ColRecord, Row, RowRecord, Sort, TableEvent, TableState,
+};
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::JsValue;
 use yew::{html, Html, NodeRef};
 
 pub type TableCard = WidgetRuntime<TableCardWidget>;
 
-static EMPTY_CELL: String = String::new();
+#[derive(Clone)]
+pub enum Msg {
+    SortBy(Col),
+}
 
 #[derive(Default)]
 pub struct TableCardWidget {
     node_refs: HashMap<Row, HashMap<Col, NodeRef>>,
+    /// The order selected by a user. Overrides the default one.
+    sort: Option<Sort>,
 }
 
 impl Widget for TableCardWidget {
-    type Event = ();
+    type Event = Msg;
     type Tag = Option<Path>;
     type Properties = SingleFlowProps;
     type Meta = SingleFlowMeta<Self>;
@@ -32,20 +42,48 @@ impl Widget for TableCardWidget {
         ctx.rewire(path);
     }
 
+    fn on_event(&mut self, event: Self::Event, ctx: &mut Context<Self>) {
+        match event {
+            Msg::SortBy(col) => {
+                let descending = self
+                    .sort
+                    .or(state_sort(ctx))
+                    .map(|sort| sort.col == col && !sort.descending)
+                    .unwrap_or_default();
+                self.sort = Some(Sort { col, descending });
+                ctx.redraw();
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let body = {
             if let Some(state) = ctx.meta().state() {
                 let cols = &state.spec.columns;
+                let sort = self.sort.or(state.spec.sort);
+                let mut rows: Vec<_> = state.rows.iter().collect();
+                if let Some(sort) = sort {
+                    let kind = cols.get(&sort.col).map(|col| col.kind).unwrap_or_default();
+                    rows.sort_by(|(_, left), (_, right)| {
+                        let ordering =
+                            compare(kind, left.cols.get(&sort.col), right.cols.get(&sort.col));
+                        if sort.descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    });
+                }
                 html! {
                     <div class="flex-grow-1 overflow-auto">
                         <table class="table table-hover">
                             <thead>
                                 <tr>
-                                    { for cols.iter().map(|col| self.render_col(col)) }
+                                    { for cols.iter().map(|col| self.render_col(col, sort, ctx)) }
                                 </tr>
                             </thead>
                             <tbody>
-                                { for state.rows.iter().map(|row| self.render_row(row, cols)) }
+                                { for rows.into_iter().map(|row| self.render_row(row, cols)) }
                             </tbody>
                         </table>
                     </div>
@@ -63,9 +101,27 @@ impl Widget for TableCardWidget {
 }
 
 impl TableCardWidget {
-    fn render_col(&self, (_col_id, col): (&Col, &ColRecord)) -> Html {
+    fn render_col(
+        &self,
+        (col_id, col): (&Col, &ColRecord),
+        sort: Option<Sort>,
+        ctx: &Context<Self>,
+    ) -> Html {
+        let arrow = match sort {
+            Some(sort) if sort.col == *col_id => {
+                if sort.descending {
+                    " ▼"
+                } else {
+                    " ▲"
+                }
+            }
+            _ => "",
+        };
         html! {
-            <th class="col-1" scope="col">{ &col.title }</th>
+            <th class="col-1" scope="col" style="cursor: pointer;"
+                onclick=ctx.event(Msg::SortBy(*col_id))>
+                { &col.title }{ arrow }
+            </th>
         }
     }
 
@@ -77,12 +133,12 @@ impl TableCardWidget {
         let cols = &row.cols;
         html! {
             <tr>
-                { for columns.keys().map(|col_id| self.render_cell(row_id, col_id, cols.get(col_id))) }
+                { for columns.iter().map(|(col_id, col)| self.render_cell(row_id, col_id, col, cols.get(col_id))) }
             </tr>
         }
     }
 
-    fn render_cell(&self, row: &Row, col: &Col, cell: Option<&String>) -> Html {
+    fn render_cell(&self, row: &Row, col: &Col, record: &ColRecord, cell: Option<&Cell>) -> Html {
         let node_ref = self
             .node_refs
             .get(row)
@@ -96,8 +152,50 @@ impl TableCardWidget {
                 );
                 NodeRef::default()
             });
+        let class = match cell.map(|cell| cell.style).unwrap_or_default() {
+            CellStyle::Normal => "",
+            CellStyle::Positive => "text-success",
+            CellStyle::Negative => "text-danger",
+            CellStyle::Muted => "text-muted",
+        };
+        let value = cell
+            .map(|cell| format_value(record, &cell.value))
+            .unwrap_or_default();
         html! {
-            <td ref=node_ref>{ cell.unwrap_or(&EMPTY_CELL) }</td>
+            <td ref=node_ref class=class>{ value }</td>
+        }
+    }
+}
+
+fn parse(value: &str) -> Option<f64> {
+    value.trim().parse().ok()
+}
+
+fn format_value(col: &ColRecord, value: &str) -> String {
+    let label = &col.label;
+    match (col.kind, parse(value)) {
+        (ColKind::Text, _) | (_, None) => value.to_string(),
+        (ColKind::Number, Some(number)) => format!("{} {}", number / label.divisor, label.caption)
+            .trim_end()
+            .to_string(),
+        (ColKind::Percentage, Some(ratio)) => format!("{:.2} %", ratio * 100.0),
+        (ColKind::Timestamp, Some(ms)) => {
+            let date = js_sys::Date::new(&ms.into());
+            date.to_locale_string("en-GB", &JsValue::UNDEFINED).into()
+        }
+    }
+}
+
+/// Compares cells by kinds of columns. Empty cells are always the lowest.
+fn compare(kind: ColKind, left: Option<&Cell>, right: Option<&Cell>) -> Ordering {
+    let left = left.map(|cell| cell.value.as_str());
+    let right = right.map(|cell| cell.value.as_str());
+    match kind {
+        ColKind::Text => left.cmp(&right),
+        ColKind::Number | ColKind::Timestamp | ColKind::Percentage => {
+            let left = left.and_then(parse).map(OrderedFloat);
+            let right = right.and_then(parse).map(OrderedFloat);
+            left.cmp(&right)
         }
     }
 }
@@ -137,23 +235,50 @@ impl WiredWidget<SingleFlowMeta<Self>> for TableCardWidget {
         _tag: &Path,
         event: &<Self::Flow as Flow>::Event,
         reloaded: &mut bool,
-        _ctx: &mut Context<Self>,
+        ctx: &mut Context<Self>,
     ) {
-        match &event {
+        let expired = ctx
+            .meta()
+            .state()
+            .map(|state| state.has_expired(event.timestamp))
+            .unwrap_or_default();
+        if expired {
+            *reloaded = true;
+            return;
+        }
+        match &event.event {
             TableEvent::SetCell { row, col, value } => {
+                let sorted_by = self.sort.or(state_sort(ctx)).map(|sort| sort.col);
+                let record = ctx
+                    .meta()
+                    .state()
+                    .and_then(|state| state.spec.columns.get(col));
                 let node = self
                     .node_refs
                     .get_mut(row)
                     .and_then(|cols| cols.get_mut(col));
-                if let Some(node_ref) = node {
-                    utils::set_node(node_ref, value);
-                } else {
-                    *reloaded = true;
+                match (node, record) {
+                    (Some(node_ref), Some(record)) if sorted_by != Some(*col) => {
+                        utils::set_node(node_ref, format_value(record, value));
+                    }
+                    _ => {
+                        *reloaded = true;
+                    }
                 }
             }
-            TableEvent::AddRow { .. } | TableEvent::DelRow { .. } => {
+            TableEvent::AddRow { .. }
+            | TableEvent::DelRow { .. }
+            | TableEvent::SetStyle { .. }
+            | TableEvent::SetRow { .. }
+            | TableEvent::UpsertRow { .. }
+            | TableEvent::SetRows { .. }
+            | TableEvent::Expire => {
                 *reloaded = true;
             }
         }
     }
 }
+
+fn state_sort(ctx: &Context<TableCardWidget>) -> Option<Sort> {
+    ctx.meta().state().and_then(|state| state.spec.sort)
+}
@@ -74,7 +74,7 @@ pub const RENDER_DEFAULT: RenderRule = RenderRule {
 pub static RENDERS: Lazy<HashMap<StreamType, RenderRule>> = Lazy::new(preffered_sizes);
 
 fn preffered_sizes() -> HashMap<StreamType, RenderRule> {
-    use super::prime;
+    use super::{prime, trade};
     use rrpack_prime::{control, transparent, visual};
     let mut preffered_sizes: HashMap<StreamType, RenderRule> = HashMap::new();
 
@@ -116,6 +116,10 @@ fn preffered_sizes() -> HashMap<StreamType, RenderRule> {
         visual::gauge::GaugeState::stream_type(),
         RenderRule::new::<prime::visual::GaugeCard, _>(300, 100, false),
     );
+    preffered_sizes.insert(
+        visual::heatmap::HeatmapState::stream_type(),
+        RenderRule::new::<prime::visual::HeatmapCard, _>(600, 400, false),
+    );
     preffered_sizes.insert(
         visual::histogram::HistogramState::stream_type(),
         RenderRule::new::<prime::visual::HistogramCard, _>(450, 300, false),
@@ -128,14 +132,43 @@ fn preffered_sizes() -> HashMap<StreamType, RenderRule> {
         visual::live_text::LiveTextState::stream_type(),
         RenderRule::new::<prime::visual::LiveTextCard, _>(450, 200, false),
     );
+    preffered_sizes.insert(
+        visual::multi_pulse::MultiPulseState::stream_type(),
+        RenderRule::new::<prime::visual::MultiPulseCard, _>(450, 300, false),
+    );
     preffered_sizes.insert(
         visual::pulse::PulseState::stream_type(),
         RenderRule::new::<prime::visual::PulseCard, _>(450, 300, false),
     );
+    preffered_sizes.insert(
+        visual::sketch::SketchState::stream_type(),
+        RenderRule::new::<prime::visual::SketchCard, _>(450, 300, false),
+    );
+    preffered_sizes.insert(
+        visual::status::StatusState::stream_type(),
+        RenderRule::new::<prime::visual::StatusCard, _>(300, 200, false),
+    );
     preffered_sizes.insert(
         visual::table::TableState::stream_type(),
         RenderRule::new::<prime::visual::TableCard, _>(800, 400, true),
     );
 
+    preffered_sizes.insert(
+        rrpack_trade::visual::ohlc::OhlcState::stream_type(),
+        RenderRule::new::<trade::visual::OhlcCard, _>(450, 300, false),
+    );
+    preffered_sizes.insert(
+        rrpack_trade::visual::order_book::OrderBookState::stream_type(),
+        RenderRule::new::<trade::visual::OrderBookCard, _>(450, 600, false),
+    );
+    preffered_sizes.insert(
+        rrpack_trade::visual::positions::PositionsState::stream_type(),
+        RenderRule::new::<trade::visual::PositionsCard, _>(800, 300, true),
+    );
+    preffered_sizes.insert(
+        rrpack_trade::visual::tape::TapeState::stream_type(),
+        RenderRule::new::<trade::visual::TapeCard, _>(300, 400, false),
+    );
+
     preffered_sizes
 }
@@ -0,0 +1,11 @@
+mod ohlc;
+pub use ohlc::OhlcCard;
+
+mod order_book;
+pub use order_book::OrderBookCard;
+
+mod positions;
+pub use positions::PositionsCard;
+
+mod tape;
+pub use tape::TapeCard;
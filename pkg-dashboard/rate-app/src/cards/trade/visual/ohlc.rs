@@ -0,0 +1,110 @@
+use crate::canvas::{self, DrawCanvas};
+use anyhow::Error;
+use rate_ui::agents::graphics::{GraphicsAgent, GraphicsResponse};
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, OnBridgeEvent, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_trade::visual::ohlc::OhlcState;
+use yew::{html, Html};
+
+pub type OhlcCard = WidgetRuntime<OhlcCardWidget>;
+
+#[derive(Default)]
+pub struct OhlcCardWidget {
+    canvas: DrawCanvas,
+}
+
+impl Widget for OhlcCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        ctx.graphics().on_frame(true);
+        ctx.graphics().track_size(self.canvas.node_ref().clone());
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn rendered(&mut self, first: bool) -> Result<(), Error> {
+        if first {
+            self.canvas.bind()?;
+            self.canvas.resize()?;
+        }
+        Ok(())
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let node_ref = self.canvas.node_ref().clone();
+        html! {
+            // IMPORTANT! It change it check the chart no continuously grow in height.
+            <div yew=module_path!() style="display: block;">
+                <canvas
+                    ref=node_ref
+                    style="width: 100%; height: 100%; padding: 0; margin: 0; box-sizing: border-box;"
+                />
+            </div>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for OhlcCardWidget {
+    type Flow = OhlcState;
+}
+
+impl OnBridgeEvent<GraphicsAgent> for OhlcCardWidget {
+    fn on_event(&mut self, event: GraphicsResponse, ctx: &mut Context<Self>) -> Result<(), Error> {
+        match event {
+            GraphicsResponse::SizeChanged(_) => {
+                self.canvas.resize()?;
+            }
+            GraphicsResponse::Frame => {
+                if let Some(state) = ctx.meta().state() {
+                    self.canvas.resize()?;
+                    self.canvas.clear()?;
+                    let interval_ms = state.spec.interval_ms();
+                    // TODO: Avoid using time here!!!
+                    let x_to = js_sys::Date::now() as i64;
+                    let mut min = f64::MAX;
+                    let mut max = f64::MIN;
+                    let candles: Vec<_> = state
+                        .frame
+                        .iter()
+                        .map(|item| {
+                            let candle = &item.event;
+                            min = min.min(candle.low);
+                            max = max.max(candle.high);
+                            let x = item.timestamp.0 - x_to + interval_ms / 2;
+                            (
+                                x,
+                                candle.open as f32,
+                                candle.high as f32,
+                                candle.low as f32,
+                                candle.close as f32,
+                            )
+                        })
+                        .collect();
+                    if candles.is_empty() {
+                        min = 0.0;
+                        max = 0.0;
+                    }
+                    self.canvas.draw_candles(
+                        state.spec.retain as i64,
+                        interval_ms,
+                        min as f32,
+                        max as f32,
+                        &canvas::formatter_sec,
+                        &canvas::formatter_plain,
+                        &candles,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::blocks;
+use crate::canvas::DrawCanvas;
+use anyhow::Error;
+use rate_ui::agents::graphics::{GraphicsAgent, GraphicsResponse};
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, OnBridgeEvent, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_trade::visual::order_book::{OrderBookState, Side};
+use yew::{html, Html};
+
+pub type OrderBookCard = WidgetRuntime<OrderBookCardWidget>;
+
+#[derive(Default)]
+pub struct OrderBookCardWidget {
+    canvas: DrawCanvas,
+}
+
+impl Widget for OrderBookCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        ctx.graphics().on_frame(true);
+        ctx.graphics().track_size(self.canvas.node_ref().clone());
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn rendered(&mut self, first: bool) -> Result<(), Error> {
+        if first {
+            self.canvas.bind()?;
+            self.canvas.resize()?;
+        }
+        Ok(())
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let ladder = {
+            if let Some(state) = ctx.meta().state() {
+                self.render_ladder(state)
+            } else {
+                blocks::spinner("Connecting...")
+            }
+        };
+        let node_ref = self.canvas.node_ref().clone();
+        html! {
+            <div yew=module_path!() class="d-flex flex-column" style="height: 100%; width: 100%;">
+                <div class="flex-grow-1 overflow-auto px-3">
+                    { ladder }
+                </div>
+                <canvas
+                    ref=node_ref
+                    style="width: 100%; height: 250px; padding: 0; margin: 0; box-sizing: border-box;"
+                />
+            </div>
+        }
+    }
+}
+
+impl OrderBookCardWidget {
+    fn render_ladder(&self, state: &OrderBookState) -> Html {
+        let mut asks: Vec<_> = state.asks().collect();
+        asks.reverse();
+        let spread = state
+            .spread()
+            .map(|spread| spread.to_string())
+            .unwrap_or_default();
+        html! {
+            <table class="table table-sm">
+                <tbody>
+                    { for asks.into_iter().map(|level| self.render_level(Side::Ask, level)) }
+                    <tr class="table-light">
+                        <td class="text-muted">{ "Spread" }</td>
+                        <td class="text-end fw-bold">{ spread }</td>
+                    </tr>
+                    { for state.bids().map(|level| self.render_level(Side::Bid, level)) }
+                </tbody>
+            </table>
+        }
+    }
+
+    fn render_level(&self, side: Side, (price, volume): (f64, f64)) -> Html {
+        let class = match side {
+            Side::Bid => "text-success",
+            Side::Ask => "text-danger",
+        };
+        html! {
+            <tr>
+                <td class=class>{ price }</td>
+                <td class="text-end">{ volume }</td>
+            </tr>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for OrderBookCardWidget {
+    type Flow = OrderBookState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
+
+impl OnBridgeEvent<GraphicsAgent> for OrderBookCardWidget {
+    fn on_event(&mut self, event: GraphicsResponse, ctx: &mut Context<Self>) -> Result<(), Error> {
+        match event {
+            GraphicsResponse::SizeChanged(_) => {
+                self.canvas.resize()?;
+            }
+            GraphicsResponse::Frame => {
+                if let Some(state) = ctx.meta().state() {
+                    self.canvas.resize()?;
+                    self.canvas.clear()?;
+                    let to_points = |levels: Vec<(f64, f64)>| -> Vec<(f32, f32)> {
+                        levels
+                            .into_iter()
+                            .map(|(price, volume)| (price as f32, volume as f32))
+                            .collect()
+                    };
+                    let bids = to_points(state.cumulative(Side::Bid));
+                    let asks = to_points(state.cumulative(Side::Ask));
+                    let min = bids.last().or_else(|| asks.first()).map(|level| level.0);
+                    let max = asks.last().or_else(|| bids.first()).map(|level| level.0);
+                    let volume = bids
+                        .last()
+                        .into_iter()
+                        .chain(asks.last())
+                        .map(|level| level.1)
+                        .fold(0.0, f32::max);
+                    if let (Some(min), Some(max)) = (min, max) {
+                        self.canvas.draw_depth(min, max, volume, &bids, &asks)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
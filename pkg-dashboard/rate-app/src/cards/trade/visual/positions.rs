@@ -0,0 +1,102 @@
+use crate::blocks;
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_trade::visual::positions::{Position, PositionsState};
+use yew::{html, Html};
+
+pub type PositionsCard = WidgetRuntime<PositionsCardWidget>;
+
+#[derive(Default)]
+pub struct PositionsCardWidget {}
+
+impl Widget for PositionsCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let body = {
+            if let Some(state) = ctx.meta().state() {
+                html! {
+                    <table class="table table-hover">
+                        <thead>
+                            <tr>
+                                <th>{ "Instrument" }</th>
+                                <th class="text-end">{ "Quantity" }</th>
+                                <th class="text-end">{ "Avg. Price" }</th>
+                                <th class="text-end">{ "Mark" }</th>
+                                <th class="text-end">{ "Realized" }</th>
+                                <th class="text-end">{ "Unrealized" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for state.positions.iter().map(|(name, pos)| self.render_position(name, pos)) }
+                        </tbody>
+                        <tfoot>
+                            <tr class="fw-bold">
+                                <td colspan="4">{ "Total" }</td>
+                                { self.render_pnl(state.realized()) }
+                                { self.render_pnl(state.unrealized()) }
+                            </tr>
+                        </tfoot>
+                    </table>
+                }
+            } else {
+                blocks::spinner("Connecting...")
+            }
+        };
+        html! {
+            <div yew=module_path!() class="overflow-auto pe-3" style="height: 100%; width: 100%;">
+                { body }
+            </div>
+        }
+    }
+}
+
+impl PositionsCardWidget {
+    fn render_position(&self, name: &str, pos: &Position) -> Html {
+        let mark = pos.mark.map(|mark| mark.to_string()).unwrap_or_default();
+        html! {
+            <tr>
+                <td>{ name }</td>
+                <td class="text-end">{ pos.quantity }</td>
+                <td class="text-end">{ format!("{:.2}", pos.avg_price) }</td>
+                <td class="text-end">{ mark }</td>
+                { self.render_pnl(pos.realized) }
+                { self.render_pnl(pos.unrealized()) }
+            </tr>
+        }
+    }
+
+    fn render_pnl(&self, value: f64) -> Html {
+        let class = if value > 0.0 {
+            "text-end text-success"
+        } else if value < 0.0 {
+            "text-end text-danger"
+        } else {
+            "text-end"
+        };
+        html! {
+            <td class=class>{ format!("{:.2}", value) }</td>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for PositionsCardWidget {
+    type Flow = PositionsState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
@@ -0,0 +1,84 @@
+use crate::blocks;
+use rate_ui::widget::wired_widget::{SingleFlowMeta, SingleFlowProps, WiredWidget};
+use rate_ui::widget::{Context, Widget, WidgetRuntime};
+use rill_protocol::io::provider::Path;
+use rrpack_basis::frames::TimedEvent;
+use rrpack_trade::visual::tape::{Direction, TapeRecord, TapeState};
+use yew::{html, Html};
+
+pub type TapeCard = WidgetRuntime<TapeCardWidget>;
+
+#[derive(Default)]
+pub struct TapeCardWidget {}
+
+impl Widget for TapeCardWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = SingleFlowProps;
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        self.on_props(ctx);
+    }
+
+    fn on_props(&mut self, ctx: &mut Context<Self>) {
+        let path = ctx.properties().path.clone().of_server();
+        ctx.rewire(path);
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let body = {
+            if let Some(state) = ctx.meta().state() {
+                html! {
+                    <table class="table table-sm">
+                        <thead>
+                            <tr>
+                                <th>{ "Time" }</th>
+                                <th class="text-end">{ "Price" }</th>
+                                <th class="text-end">{ "Volume" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for state.frame.iter().rev().map(|record| self.render_record(record)) }
+                        </tbody>
+                    </table>
+                }
+            } else {
+                blocks::spinner("Connecting...")
+            }
+        };
+        html! {
+            <div yew=module_path!() class="overflow-auto pe-3" style="height: 100%; width: 100%;">
+                { body }
+            </div>
+        }
+    }
+}
+
+impl TapeCardWidget {
+    fn render_record(&self, record: &TimedEvent<TapeRecord>) -> Html {
+        let ms = record.timestamp.0 as f64;
+        let date = js_sys::Date::new(&ms.into());
+        let time: String = date.to_locale_time_string("en-GB").into();
+        let trade = &record.event;
+        let class = match trade.direction {
+            Direction::Buy => "text-success",
+            Direction::Sell => "text-danger",
+        };
+        html! {
+            <tr class=class>
+                <td>{ time }</td>
+                <td class="text-end">{ trade.price }</td>
+                <td class="text-end">{ trade.volume }</td>
+            </tr>
+        }
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for TapeCardWidget {
+    type Flow = TapeState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        ctx.redraw();
+    }
+}
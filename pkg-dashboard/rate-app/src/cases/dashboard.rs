@@ -3,6 +3,7 @@ use crate::welcome::state::{GlobalScene, SceneState, SCENE};
 use anyhow::Error;
 use rate_ui::shared_object::{DataChanged, SharedObject};
 use rate_ui::widget::{Context, NotificationHandler, Widget, WidgetRuntime};
+use rrpack_basis::manifest::issues::LayoutIssue;
 use yew::{html, Html};
 
 pub type Dashboard = WidgetRuntime<DashboardWidget>;
@@ -47,10 +48,17 @@ impl Widget for DashboardWidget {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let state = self.cases.read();
+        // Files that can't be parsed have no layouts
+        let mut unparsed = state.get_issues(None).peekable();
         if let Some(layout) = state.get_layout_tab() {
             html! {
-                <super::LayoutViewer layout=layout.clone() />
+                <div>
+                    { self.render_issues(state.get_issues(Some(&layout.name))) }
+                    <super::LayoutViewer layout=layout.clone() />
+                </div>
             }
+        } else if unparsed.peek().is_some() {
+            self.render_issues(unparsed)
         } else {
             html! {
                 <div>
@@ -64,6 +72,21 @@ impl Widget for DashboardWidget {
     }
 }
 
+impl DashboardWidget {
+    fn render_issues<'a>(&self, issues: impl Iterator<Item = &'a LayoutIssue>) -> Html {
+        issues
+            .map(|issue| {
+                html! {
+                    <div class="alert alert-danger">
+                        <strong>{ "Layout error: " }</strong>
+                        { issue.to_string() }
+                    </div>
+                }
+            })
+            .collect()
+    }
+}
+
 impl NotificationHandler<DataChanged<CasesState>> for DashboardWidget {
     fn handle(
         &mut self,
@@ -1,4 +1,5 @@
 use super::record::Record;
+use super::tabs_view::TabsView;
 use crate::explorer::state::PATHS;
 use rate_ui::shared_object::SharedObject;
 use yew::{html, Html};
@@ -26,6 +27,10 @@ impl LayoutRender for Element {
             Self::Spacer(value) => value.layout_render(),
             Self::Row(value) => value.layout_render(),
             Self::Column(value) => value.layout_render(),
+            Self::Grid(value) => value.layout_render(),
+            Self::Tabs(value) => value.layout_render(),
+            Self::Section(value) => value.layout_render(),
+            Self::SizedBox(value) => value.layout_render(),
 
             Self::Text(value) => value.layout_render(),
             Self::Flow(value) => value.layout_render(),
@@ -119,6 +124,81 @@ impl LayoutRender for Column {
     }
 }
 
+use rrpack_basis::manifest::layouts::components::{Grid, GridItem};
+
+impl LayoutRender for Grid {
+    fn layout_render(&self) -> Html {
+        let style = format!(
+            "display: grid; grid-template-columns: repeat({}, 1fr);",
+            self.columns.max(1)
+        );
+        html! {
+            <div yew="Grid" style=style>
+                { for self.items.iter().map(LayoutRender::layout_render) }
+            </div>
+        }
+    }
+}
+
+impl LayoutRender for GridItem {
+    fn layout_render(&self) -> Html {
+        let style = format!(
+            "grid-column: span {}; grid-row: span {};",
+            self.col_span.max(1),
+            self.row_span.max(1)
+        );
+        html! {
+            <div yew="GridItem" class="d-flex flex-column" style=style>
+                { self.child.layout_render() }
+            </div>
+        }
+    }
+}
+
+use rrpack_basis::manifest::layouts::components::Tabs;
+
+impl LayoutRender for Tabs {
+    fn layout_render(&self) -> Html {
+        html! {
+            <TabsView tabs=self.clone() />
+        }
+    }
+}
+
+use rrpack_basis::manifest::layouts::components::Section;
+
+impl LayoutRender for Section {
+    fn layout_render(&self) -> Html {
+        let open = !self.collapsed;
+        html! {
+            <details yew="Section" class="d-flex flex-column" open=open>
+                <summary class="caption">{ &self.title }</summary>
+                { self.child.layout_render() }
+            </details>
+        }
+    }
+}
+
+use rrpack_basis::manifest::layouts::components::SizedBox;
+
+impl LayoutRender for SizedBox {
+    fn layout_render(&self) -> Html {
+        let width = self
+            .width
+            .map(|width| format!("width: {}px;", width))
+            .unwrap_or_default();
+        let height = self
+            .height
+            .map(|height| format!("height: {}px;", height))
+            .unwrap_or_default();
+        html! {
+            <div yew="SizedBox" class="d-flex flex-column" style=format!("{}{}", width, height)>
+                { self.child.layout_render() }
+            </div>
+        }
+    }
+}
+
 use rrpack_basis::manifest::layouts::components::Text;
 
 impl LayoutRender for Text {
@@ -151,9 +231,27 @@ impl LayoutRender for Flow {
         let paths = PATHS.with(SharedObject::clone);
         let paths = paths.read();
         if let Some(desc) = paths.descs.get(&self.path) {
-            Record::from(desc).render()
+            let card = Record::from(desc).render();
+            // Flows without sizes are rendered as is
+            if let Some(size) = self.size.as_ref() {
+                let style = format!(
+                    "min-width: {}px; min-height: {}px;",
+                    size.width, size.height
+                );
+                html! {
+                    <div yew="Flow" class="d-flex" style=style>
+                        { card }
+                    </div>
+                }
+            } else {
+                card
+            }
         } else {
-            html! {}
+            html! {
+                <div yew="Flow" class="alert alert-warning">
+                    { format!("Flow not found: {}", self.path) }
+                </div>
+            }
         }
     }
 }
@@ -3,6 +3,7 @@ use rate_ui::shared_object::SharedObject;
 use rate_ui::widget::wired_widget::{SingleFlowMeta, WiredWidget};
 use rate_ui::widget::{Context, Widget, WidgetRuntime};
 use rill_protocol::io::provider::Path;
+use rrpack_basis::manifest::issues::{IssuesSpec, IssuesState};
 use rrpack_basis::manifest::layouts::{LayoutsSpec, LayoutsState};
 use yew::Html;
 
@@ -61,3 +62,47 @@ impl WiredWidget<SingleFlowMeta<Self>> for LoaderWidget {
         }
     }
 }
+
+pub type IssuesLoader = WidgetRuntime<IssuesLoaderWidget>;
+
+pub struct IssuesLoaderWidget {
+    cases: SharedObject<CasesState>,
+}
+
+impl Default for IssuesLoaderWidget {
+    fn default() -> Self {
+        Self {
+            cases: CASES.with(SharedObject::clone),
+        }
+    }
+}
+
+impl Widget for IssuesLoaderWidget {
+    type Event = ();
+    type Tag = Option<Path>;
+    type Properties = ();
+    type Meta = SingleFlowMeta<Self>;
+
+    fn init(&mut self, ctx: &mut Context<Self>) {
+        let path = IssuesSpec::path().of_server();
+        ctx.rewire(path);
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        Html::default()
+    }
+}
+
+impl WiredWidget<SingleFlowMeta<Self>> for IssuesLoaderWidget {
+    type Flow = IssuesState;
+
+    fn state_changed(&mut self, _reloaded: bool, ctx: &mut Context<Self>) {
+        let issues = ctx
+            .meta()
+            .state()
+            .map(|state| state.issues.values().flatten().cloned().collect())
+            .unwrap_or_default();
+        let mut cases = self.cases.write();
+        cases.issues = issues;
+    }
+}
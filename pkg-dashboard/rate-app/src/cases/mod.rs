@@ -9,12 +9,14 @@ pub use layout_viewer::LayoutViewer;
 
 mod layout_render;
 
+mod tabs_view;
+
 pub mod record;
 
 pub mod state;
 
 mod loader;
-pub use loader::Loader;
+pub use loader::{IssuesLoader, Loader};
 
 mod tab_selector;
 use tab_selector::TabSelector;
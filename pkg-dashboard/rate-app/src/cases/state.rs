@@ -1,6 +1,7 @@
 use rate_ui::shared_object::{RouterState, SharedObject};
 use rate_ui::storage::typed_storage::Storable;
 use rill_protocol::io::provider::{EntryId, Path};
+use rrpack_basis::manifest::issues::LayoutIssue;
 use rrpack_basis::manifest::layouts::components::Layout;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
@@ -43,6 +44,10 @@ pub struct CasesState {
     pub selected_tab: Option<EntryId>,
 
     pub tabs: BTreeMap<Path, Layout>,
+
+    /// Problems of layout files reported by the server.
+    #[serde(skip)]
+    pub issues: Vec<LayoutIssue>,
 }
 
 impl CasesState {
@@ -72,6 +77,16 @@ impl CasesState {
         let path: Path = vec![selected_layout.clone(), selected_tab.clone()].into();
         self.tabs.get(&path)
     }
+
+    /// Issues of the layout or issues of files without layouts if it's not set.
+    pub fn get_issues<'a>(
+        &'a self,
+        layout: Option<&'a Path>,
+    ) -> impl Iterator<Item = &'a LayoutIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.layout.as_ref() == layout)
+    }
 }
 
 impl Storable for CasesState {
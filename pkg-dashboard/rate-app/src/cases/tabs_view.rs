@@ -0,0 +1,62 @@
+use super::layout_render::LayoutRender;
+use rate_ui::widget::{Context, Widget, WidgetRuntime};
+use rrpack_basis::manifest::layouts::components::Tabs;
+use yew::{html, Html, Properties};
+
+pub type TabsView = WidgetRuntime<TabsViewWidget>;
+
+#[derive(Debug, Properties, Clone, PartialEq, Eq)]
+pub struct Props {
+    pub tabs: Tabs,
+}
+
+#[derive(Default)]
+pub struct TabsViewWidget {
+    selected: usize,
+}
+
+#[derive(Clone)]
+pub enum Msg {
+    SelectTab(usize),
+}
+
+impl Widget for TabsViewWidget {
+    type Event = Msg;
+    type Tag = ();
+    type Properties = Props;
+    type Meta = ();
+
+    fn on_event(&mut self, event: Self::Event, ctx: &mut Context<Self>) {
+        match event {
+            Msg::SelectTab(idx) => {
+                self.selected = idx;
+                ctx.redraw();
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let tabs = &ctx.properties().tabs.tabs;
+        // The amount of tabs could be reduced by an update
+        let selected = self.selected.min(tabs.len().saturating_sub(1));
+        html! {
+            <div yew=module_path!() class="d-flex flex-column">
+                <nav class="nav nav-tabs">
+                    { for tabs.iter().enumerate().map(|(idx, tab)| {
+                        let class = if idx == selected {
+                            "nav-link link-primary active"
+                        } else {
+                            "nav-link link-secondary"
+                        };
+                        html! {
+                            <div class="nav-item click">
+                                <a class=class onclick=ctx.event(Msg::SelectTab(idx))>{ &tab.title }</a>
+                            </div>
+                        }
+                    }) }
+                </nav>
+                { for tabs.get(selected).map(|tab| tab.child.layout_render()) }
+            </div>
+        }
+    }
+}
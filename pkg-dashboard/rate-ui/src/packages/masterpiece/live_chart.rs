@@ -1,3 +1,4 @@
+use crate::packages::masterpiece::palette;
 use crate::packages::masterpiece::scale::{LinearScale, Range};
 use crate::packages::or_fail::{Fail, Fasten};
 use anyhow::Error;
@@ -19,6 +20,12 @@ pub struct Point {
     pub timestamp: f64,
 }
 
+/// A series of points that rendered as a single line.
+pub struct Line<I> {
+    pub color: Option<String>,
+    pub points: I,
+}
+
 pub struct LiveChart {
     last_timestamp: f64,
     canvas_ref: NodeRef,
@@ -91,6 +98,24 @@ impl LiveChart {
         &mut self,
         timestamp: f64,
         points: I,
+        y_domain: Range,
+        x_domain: Range,
+    ) -> Result<(), Error>
+    where
+        I: Iterator<Item = Point>,
+    {
+        let line = Line {
+            color: None,
+            points,
+        };
+        self.render_lines(timestamp, vec![line], y_domain, x_domain)
+    }
+
+    /// Renders multiple lines on the same canvas.
+    pub fn render_lines<I>(
+        &mut self,
+        timestamp: f64,
+        lines: Vec<Line<I>>,
         mut y_domain: Range,
         _x_domain: Range,
     ) -> Result<(), Error>
@@ -141,26 +166,31 @@ impl LiveChart {
 
         // DRAW LINES
         ctx.set_fill_style(&JsValue::from("#EEEEEE"));
-        ctx.set_stroke_style(&JsValue::from("#5d2f86"));
         ctx.set_line_width(LINE_WIDTH);
 
-        let mut points_iter = points;
-        if let Some(mut last_point) = points_iter.next() {
-            ctx.move_to(ts_range.min(), translate_y(last_point.value));
-            ctx.begin_path();
-            for next_point in points_iter {
-                ctx.line_to(
-                    translate_x(next_point.timestamp),
-                    translate_y(last_point.value),
-                );
-                ctx.line_to(
-                    translate_x(next_point.timestamp),
-                    translate_y(next_point.value),
-                );
-                last_point = next_point;
+        for (idx, line) in lines.into_iter().enumerate() {
+            let color = line
+                .color
+                .unwrap_or_else(|| palette::color(idx).to_string());
+            ctx.set_stroke_style(&JsValue::from(color));
+            let mut points_iter = line.points;
+            if let Some(mut last_point) = points_iter.next() {
+                ctx.begin_path();
+                ctx.move_to(ts_range.min(), translate_y(last_point.value));
+                for next_point in points_iter {
+                    ctx.line_to(
+                        translate_x(next_point.timestamp),
+                        translate_y(last_point.value),
+                    );
+                    ctx.line_to(
+                        translate_x(next_point.timestamp),
+                        translate_y(next_point.value),
+                    );
+                    last_point = next_point;
+                }
+                ctx.line_to(ts_range.max(), translate_y(last_point.value));
+                ctx.stroke();
             }
-            ctx.line_to(ts_range.max(), translate_y(last_point.value));
-            ctx.stroke();
         }
         Ok(())
     }
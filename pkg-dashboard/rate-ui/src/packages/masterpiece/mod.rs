@@ -1,2 +1,3 @@
 pub mod live_chart;
+pub mod palette;
 pub mod scale;
@@ -0,0 +1,10 @@
+//! Colors of series used if a color is not specified explicitly.
+
+const COLORS: &[&str] = &[
+    "#5d2f86", "#d20909", "#1e88e5", "#43a047", "#fb8c00", "#00897b",
+];
+
+/// Returns a CSS color (`#RRGGBB`) of the series with the index.
+pub fn color(idx: usize) -> &'static str {
+    COLORS[idx % COLORS.len()]
+}
@@ -0,0 +1,5 @@
+//! The APM pack: metrics of the process, the host and
+//! the `tokio` runtime (with the `tokio-runtime` feature).
+
+#[cfg(feature = "engine")]
+pub mod system;
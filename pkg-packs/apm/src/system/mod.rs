@@ -0,0 +1,114 @@
+//! Metrics of the process and the host.
+
+pub mod procfs;
+
+use rill_protocol::flow::core::FlowMode;
+use rrpack_prime::visual::{Pulse, PulseOpts};
+use std::io::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The default interval of sampling.
+pub const INTERVAL: Duration = Duration::from_secs(1);
+
+const MB: f64 = 1_048_576.0;
+
+/// Tracers of system metrics registered under `app.system.*` paths.
+#[derive(Debug, Clone)]
+pub struct SystemMetrics {
+    cpu: Pulse,
+    memory: Pulse,
+    fds: Pulse,
+    threads: Pulse,
+    load: Pulse,
+}
+
+impl Default for SystemMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemMetrics {
+    pub fn new() -> Self {
+        Self {
+            cpu: pulse("app.system.process.cpu", PulseOpts::default().suffix("%")),
+            memory: pulse(
+                "app.system.process.memory",
+                PulseOpts::default().suffix("MB").divisor(MB),
+            ),
+            fds: pulse("app.system.process.fds", PulseOpts::default()),
+            threads: pulse("app.system.process.threads", PulseOpts::default()),
+            load: pulse("app.system.host.load", PulseOpts::default()),
+        }
+    }
+
+    /// Samples metrics in a separate thread with the interval.
+    pub fn spawn(self, interval: Duration) -> Result<(), Error> {
+        thread::Builder::new()
+            .name("rillrate-system".into())
+            .spawn(move || self.routine(interval))?;
+        Ok(())
+    }
+
+    fn routine(&self, interval: Duration) {
+        let mut prev_cpu = None;
+        loop {
+            if let Err(err) = self.sample(&mut prev_cpu) {
+                log::error!("System metrics are not available: {}", err);
+                break;
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    fn sample(&self, prev_cpu: &mut Option<(Instant, f64)>) -> Result<(), Error> {
+        let now = Instant::now();
+        let stat = procfs::read_process()?;
+        if let Some((ts, cpu_time)) = prev_cpu.replace((now, stat.cpu_time)) {
+            let elapsed = now.duration_since(ts).as_secs_f64();
+            if elapsed > 0.0 {
+                self.cpu.push((stat.cpu_time - cpu_time) / elapsed * 100.0);
+            }
+        }
+        self.memory.push(stat.rss as f64);
+        self.fds.push(stat.fds as f64);
+        self.threads.push(stat.threads as f64);
+        self.load.push(procfs::read_load_average()?);
+        Ok(())
+    }
+}
+
+fn pulse(path: &str, opts: PulseOpts) -> Pulse {
+    Pulse::new(path, FlowMode::Realtime, opts.min(0.0))
+}
+
+/// Tracks how late a task wakes up after sleeping for the `interval`
+/// on the runtime of the `handle`. The lag grows when workers are busy
+/// or blocked, since the task can't be polled in time.
+#[cfg(feature = "tokio-runtime")]
+pub fn track_runtime(handle: &tokio::runtime::Handle, interval: Duration) {
+    let lag = pulse("app.system.tokio.lag", PulseOpts::default().suffix("ms"));
+    handle.spawn(async move {
+        loop {
+            let started = Instant::now();
+            tokio::time::sleep(interval).await;
+            let late = started.elapsed().saturating_sub(interval);
+            lag.push(late.as_secs_f64() * 1_000.0);
+        }
+    });
+}
+
+/// Installs system metrics with the default interval.
+///
+/// The lag of the current `tokio` runtime is tracked as well
+/// if the `tokio-runtime` feature is enabled and it's called inside a runtime.
+pub fn install() -> Result<(), Error> {
+    SystemMetrics::new().spawn(INTERVAL)?;
+    #[cfg(feature = "tokio-runtime")]
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => track_runtime(&handle, INTERVAL),
+        Err(err) => log::warn!("The lag of the runtime is not tracked: {}", err),
+    }
+    Ok(())
+}
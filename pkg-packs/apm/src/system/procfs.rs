@@ -0,0 +1,105 @@
+//! Parsers of `/proc` files (Linux only).
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+
+/// Clock ticks per second used for CPU times in `/proc/<pid>/stat` (`USER_HZ`).
+fn ticks_per_sec() -> Result<f64, Error> {
+    // SAFETY: `sysconf` only reads a system constant
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        Ok(ticks as f64)
+    } else {
+        Err(Error::last_os_error())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessStat {
+    /// User and system CPU time in seconds.
+    pub cpu_time: f64,
+    /// Resident set size in bytes.
+    pub rss: u64,
+    pub threads: u64,
+    pub fds: u64,
+}
+
+pub fn read_process() -> Result<ProcessStat, Error> {
+    let stat = fs::read_to_string("/proc/self/stat")?;
+    let status = fs::read_to_string("/proc/self/status")?;
+    let fds = fs::read_dir("/proc/self/fd")?.count() as u64;
+    let cpu_ticks = parse_cpu_ticks(&stat).ok_or_else(|| invalid("stat"))?;
+    let rss = parse_status_field(&status, "VmRSS").ok_or_else(|| invalid("status"))?;
+    let threads = parse_status_field(&status, "Threads").ok_or_else(|| invalid("status"))?;
+    Ok(ProcessStat {
+        cpu_time: cpu_ticks as f64 / ticks_per_sec()?,
+        // The value is in kB
+        rss: rss * 1_024,
+        threads,
+        fds,
+    })
+}
+
+/// Reads the load average of the host for the last minute.
+pub fn read_load_average() -> Result<f64, Error> {
+    let loadavg = fs::read_to_string("/proc/loadavg")?;
+    parse_load_average(&loadavg).ok_or_else(|| invalid("loadavg"))
+}
+
+fn invalid(file: &str) -> Error {
+    let reason = format!("unexpected format of /proc/{}", file);
+    Error::new(ErrorKind::InvalidData, reason)
+}
+
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    // The name of a process is in parens and can contain spaces
+    let rest = &stat[stat.rfind(')')? + 1..];
+    // Fields after the name start from the 3rd, `utime` and `stime` are 14th and 15th
+    let mut fields = rest.split_whitespace();
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(utime + stime)
+}
+
+fn parse_status_field(status: &str, name: &str) -> Option<u64> {
+    status
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+fn parse_load_average(loadavg: &str) -> Option<f64> {
+    loadavg.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_files() {
+        let stat = "42 (my app) S 1 42 42 0 -1 4194560 1200 0 0 0 250 50 0 0 20 0 9 0";
+        assert_eq!(parse_cpu_ticks(stat), Some(300));
+        let status = "Name:\tmy app\nVmRSS:\t  10240 kB\nThreads:\t9\n";
+        assert_eq!(parse_status_field(status, "VmRSS"), Some(10_240));
+        assert_eq!(parse_status_field(status, "Threads"), Some(9));
+        assert_eq!(parse_status_field(status, "VmSwap"), None);
+        assert_eq!(
+            parse_load_average("0.52 0.58 0.59 2/1024 4242\n"),
+            Some(0.52)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_process() {
+        let stat = read_process().unwrap();
+        assert!(stat.rss > 0);
+        assert!(stat.threads > 0);
+        assert!(stat.fds > 0);
+        assert!(ticks_per_sec().unwrap() > 0.0);
+    }
+}
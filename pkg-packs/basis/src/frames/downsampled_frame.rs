@@ -0,0 +1,157 @@
+use super::TimedEvent;
+use derive_more::Deref;
+use rill_protocol::io::provider::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Aggregated values of an interval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bucket {
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Bucket {
+    fn new(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
+    }
+
+    pub fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Keeps values aggregated by intervals to retain long periods cheaply.
+#[derive(Debug, Clone, Serialize, Deserialize, Deref)]
+pub struct DownsampledFrame {
+    depth_ms: i64,
+    interval_ms: i64,
+    /// Every bucket has a timestamp of the beginning of its interval.
+    #[deref]
+    buckets: VecDeque<TimedEvent<Bucket>>,
+}
+
+impl DownsampledFrame {
+    pub fn new(depth_ms: i64, interval_ms: i64) -> Self {
+        Self {
+            depth_ms,
+            interval_ms: interval_ms.max(1),
+            buckets: VecDeque::new(),
+        }
+    }
+
+    /// Adds a value to the bucket of its interval and drops expired buckets.
+    pub fn insert_pop(&mut self, item: TimedEvent<f64>) {
+        let ts = item.timestamp.0;
+        let start = ts - ts.rem_euclid(self.interval_ms);
+        match self.buckets.back_mut() {
+            Some(last) if last.timestamp.0 == start => {
+                last.event.add(item.event);
+            }
+            _ => {
+                let bucket = TimedEvent {
+                    timestamp: Timestamp(start),
+                    event: Bucket::new(item.event),
+                };
+                self.buckets.push_back(bucket);
+            }
+        }
+        while let Some(front) = self.buckets.front() {
+            if (ts - front.timestamp.0) >= self.depth_ms {
+                self.buckets.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn depth_ms(&self) -> i64 {
+        self.depth_ms
+    }
+
+    pub fn interval_ms(&self) -> i64 {
+        self.interval_ms
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    fn insert(frame: &mut DownsampledFrame, ts: i64, value: f64) {
+        let item = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: value,
+        };
+        frame.insert_pop(item);
+    }
+
+    fn starts(frame: &DownsampledFrame) -> Vec<i64> {
+        frame.iter().map(|bucket| bucket.timestamp.0).collect()
+    }
+
+    #[test]
+    fn test_bucket_boundaries() {
+        let mut frame = DownsampledFrame::new(10_000, 1_000);
+        insert(&mut frame, 0, 1.0);
+        insert(&mut frame, 999, 1.0);
+        insert(&mut frame, 1_000, 1.0);
+        insert(&mut frame, 2_500, 1.0);
+        assert_eq!(starts(&frame), vec![0, 1_000, 2_000]);
+        assert_eq!(frame[0].event.count, 2);
+        assert_eq!(frame[1].event.count, 1);
+        // Negative timestamps are aligned to the beginning of intervals too
+        let mut frame = DownsampledFrame::new(10_000, 1_000);
+        insert(&mut frame, -1, 1.0);
+        assert_eq!(starts(&frame), vec![-1_000]);
+    }
+
+    #[test]
+    fn test_bucket_aggregation() {
+        let mut frame = DownsampledFrame::new(10_000, 1_000);
+        for value in &[4.0, -2.0, 10.0, 0.0] {
+            insert(&mut frame, 500, *value);
+        }
+        let bucket = &frame[0].event;
+        assert_eq!(bucket.min, -2.0);
+        assert_eq!(bucket.max, 10.0);
+        assert_eq!(bucket.sum, 12.0);
+        assert_eq!(bucket.count, 4);
+        assert_eq!(bucket.avg(), 3.0);
+    }
+
+    #[test]
+    fn test_bucket_eviction() {
+        let mut frame = DownsampledFrame::new(3_000, 1_000);
+        for ts in &[0, 1_000, 2_000] {
+            insert(&mut frame, *ts, 1.0);
+        }
+        assert_eq!(starts(&frame), vec![0, 1_000, 2_000]);
+        insert(&mut frame, 3_000, 1.0);
+        assert_eq!(starts(&frame), vec![1_000, 2_000, 3_000]);
+        // A gap removes all buckets older than the depth
+        insert(&mut frame, 10_500, 1.0);
+        assert_eq!(starts(&frame), vec![10_000]);
+        frame.clear();
+        assert!(frame.is_empty());
+    }
+}
@@ -36,7 +36,7 @@ impl<T> Frame<T> {
 
     pub fn insert_pop(&mut self, item: T) -> Option<T> {
         let result = {
-            if self.frame.len() > self.size as usize {
+            if self.frame.len() >= self.size as usize {
                 self.frame.pop_front()
             } else {
                 None
@@ -46,6 +46,10 @@ impl<T> Frame<T> {
         result
     }
 
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
+        self.frame.iter_mut()
+    }
+
     pub fn size(&self) -> u32 {
         self.size
     }
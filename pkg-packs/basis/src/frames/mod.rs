@@ -1,3 +1,6 @@
+mod downsampled_frame;
+pub use downsampled_frame::{Bucket, DownsampledFrame};
+
 mod frame;
 pub use frame::Frame;
 
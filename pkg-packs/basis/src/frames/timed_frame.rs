@@ -1,5 +1,6 @@
 use super::TimedEvent;
 use derive_more::Deref;
+use rill_protocol::io::provider::Timestamp;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -7,7 +8,10 @@ static DEFAULT_DEPTH: i64 = 60_000;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Deref)]
 pub struct TimedFrame<T> {
-    depth_ms: i64,
+    /// Max age of items. Items never expire if not set.
+    depth_ms: Option<i64>,
+    /// Max amount of items. Unlimited if not set.
+    limit: Option<u32>,
     #[deref]
     frame: VecDeque<TimedEvent<T>>,
 }
@@ -20,25 +24,108 @@ impl<T> Default for TimedFrame<T> {
 
 impl<T> TimedFrame<T> {
     pub fn new(depth_ms: i64) -> Self {
+        Self::with_limits(Some(depth_ms), None)
+    }
+
+    /// Creates a frame that retains items by both age and count.
+    pub fn with_limits(depth_ms: Option<i64>, limit: Option<u32>) -> Self {
         Self {
             depth_ms,
+            limit,
             frame: VecDeque::new(),
         }
     }
 
-    pub fn insert_pop(&mut self, item: TimedEvent<T>) {
+    /// Inserts an item and returns all expired items.
+    pub fn insert_pop(&mut self, item: TimedEvent<T>) -> Vec<TimedEvent<T>> {
+        let mut expired = Vec::new();
         while let Some(front) = self.frame.front() {
-            if (item.timestamp.0 - front.timestamp.0) >= self.depth_ms {
-                self.frame.pop_front();
+            let too_old = self
+                .depth_ms
+                .map(|depth_ms| (item.timestamp.0 - front.timestamp.0) >= depth_ms)
+                .unwrap_or_default();
+            let too_many = self
+                .limit
+                .map(|limit| self.frame.len() >= limit as usize)
+                .unwrap_or_default();
+            if too_old || too_many {
+                expired.extend(self.frame.pop_front());
             } else {
                 break;
             }
         }
         self.frame.push_back(item);
+        expired
+    }
+
+    /// Inserts an item keeping items ordered by timestamps.
+    /// Returns `false` if the item is older than the depth of the frame.
+    pub fn insert_ordered(&mut self, item: TimedEvent<T>) -> bool {
+        let is_newest = self
+            .frame
+            .back()
+            .map(|last| last.timestamp <= item.timestamp)
+            .unwrap_or(true);
+        if is_newest {
+            self.insert_pop(item);
+            return true;
+        }
+        let too_old = self
+            .depth_ms
+            .zip(self.frame.back())
+            .map(|(depth_ms, last)| (last.timestamp.0 - item.timestamp.0) >= depth_ms)
+            .unwrap_or_default();
+        if too_old {
+            return false;
+        }
+        let idx = self
+            .frame
+            .iter()
+            .position(|other| other.timestamp > item.timestamp)
+            .unwrap_or(self.frame.len());
+        self.frame.insert(idx, item);
+        if let Some(limit) = self.limit {
+            while self.frame.len() > limit as usize {
+                self.frame.pop_front();
+            }
+        }
+        true
     }
 
-    pub fn depth_ms(&self) -> i64 {
+    /// Checks that the oldest item is older than the depth of the frame at the `timestamp`.
+    pub fn has_expired(&self, timestamp: Timestamp) -> bool {
         self.depth_ms
+            .zip(self.frame.front())
+            .map(|(depth_ms, front)| (timestamp.0 - front.timestamp.0) >= depth_ms)
+            .unwrap_or_default()
+    }
+
+    /// Removes items that are older than the depth of the frame at the `timestamp`.
+    pub fn expire(&mut self, timestamp: Timestamp) -> Vec<TimedEvent<T>> {
+        let mut expired = Vec::new();
+        while self.has_expired(timestamp) {
+            expired.extend(self.frame.pop_front());
+        }
+        expired
+    }
+
+    /// Gives mutable access to items. Timestamps should not be changed.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut TimedEvent<T>> {
+        self.frame.iter_mut()
+    }
+
+    pub fn depth_ms(&self) -> Option<i64> {
+        self.depth_ms
+    }
+
+    pub fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+
+    /// Changes the max amount of items.
+    /// It will be applied on the next insert.
+    pub fn set_limit(&mut self, limit: Option<u32>) {
+        self.limit = limit;
     }
 
     pub fn clear(&mut self) {
@@ -49,3 +136,98 @@ impl<T> TimedFrame<T> {
 pub fn new_tf<T>(secs: i64) -> TimedFrame<T> {
     TimedFrame::new((secs + 1) * 1_000)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(frame: &mut TimedFrame<u32>, ts: i64) -> Vec<u32> {
+        let item = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: ts as u32,
+        };
+        frame
+            .insert_pop(item)
+            .into_iter()
+            .map(|item| item.event)
+            .collect()
+    }
+
+    fn events(frame: &TimedFrame<u32>) -> Vec<u32> {
+        frame.iter().map(|item| item.event).collect()
+    }
+
+    #[test]
+    fn test_count_limit() {
+        let mut frame = TimedFrame::with_limits(None, Some(3));
+        for ts in 1..=3 {
+            assert!(insert(&mut frame, ts).is_empty());
+        }
+        assert_eq!(insert(&mut frame, 1_000_000), vec![1]);
+        assert_eq!(events(&frame), vec![2, 3, 1_000_000]);
+        frame.set_limit(Some(1));
+        assert_eq!(insert(&mut frame, 1_000_001), vec![2, 3, 1_000_000]);
+        assert_eq!(events(&frame), vec![1_000_001]);
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let mut frame = TimedFrame::new(100);
+        assert!(insert(&mut frame, 0).is_empty());
+        assert!(insert(&mut frame, 50).is_empty());
+        assert!(insert(&mut frame, 99).is_empty());
+        assert_eq!(insert(&mut frame, 150), vec![0, 50]);
+        assert_eq!(events(&frame), vec![99, 150]);
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut frame = TimedFrame::new(100);
+        assert!(!frame.has_expired(Timestamp(1_000)));
+        insert(&mut frame, 0);
+        insert(&mut frame, 50);
+        assert!(!frame.has_expired(Timestamp(99)));
+        assert!(frame.has_expired(Timestamp(100)));
+        let expired: Vec<_> = frame
+            .expire(Timestamp(120))
+            .into_iter()
+            .map(|item| item.event)
+            .collect();
+        assert_eq!(expired, vec![0]);
+        assert_eq!(events(&frame), vec![50]);
+        assert!(TimedFrame::<u32>::with_limits(None, Some(1))
+            .expire(Timestamp(1_000))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_both_limits() {
+        let mut frame = TimedFrame::with_limits(Some(100), Some(3));
+        for ts in 0..3 {
+            insert(&mut frame, ts);
+        }
+        // The count limit is reached first
+        assert_eq!(insert(&mut frame, 3), vec![0]);
+        // The depth limit removes more items than the count limit
+        assert_eq!(insert(&mut frame, 102), vec![1, 2]);
+        assert_eq!(events(&frame), vec![3, 102]);
+    }
+
+    #[test]
+    fn test_insert_ordered() {
+        let mut frame = TimedFrame::with_limits(Some(100), Some(3));
+        insert(&mut frame, 10);
+        insert(&mut frame, 50);
+        let item = |ts: i64| TimedEvent {
+            timestamp: Timestamp(ts),
+            event: ts as u32,
+        };
+        assert!(frame.insert_ordered(item(30)));
+        assert_eq!(events(&frame), vec![10, 30, 50]);
+        // The count limit removes the oldest item
+        assert!(frame.insert_ordered(item(20)));
+        assert_eq!(events(&frame), vec![20, 30, 50]);
+        assert!(!frame.insert_ordered(item(-50)));
+        assert_eq!(events(&frame), vec![20, 30, 50]);
+    }
+}
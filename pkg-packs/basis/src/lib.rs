@@ -11,4 +11,5 @@ pub fn init() {
     // TODO: How to do it better?
     let _ = &*crate::manifest::paths::global::PATHS;
     let _ = &*crate::manifest::layouts::global::LAYOUTS;
+    let _ = &*crate::manifest::issues::global::ISSUES;
 }
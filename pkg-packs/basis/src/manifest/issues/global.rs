@@ -0,0 +1,4 @@
+use super::tracer::IssuesTracer;
+use once_cell::sync::Lazy;
+
+pub static ISSUES: Lazy<IssuesTracer> = Lazy::new(IssuesTracer::new);
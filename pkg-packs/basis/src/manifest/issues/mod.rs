@@ -0,0 +1,10 @@
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
+
+#[cfg(feature = "engine")]
+pub mod global;
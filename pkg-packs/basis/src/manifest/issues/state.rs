@@ -0,0 +1,83 @@
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::{Path, StreamType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+impl IssuesSpec {
+    pub fn path() -> Path {
+        "rillrate.manifest.issues".parse().unwrap()
+    }
+}
+
+/// A problem of a layout file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LayoutIssue {
+    pub file: String,
+    /// Lines and columns start from `1`.
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// The name of a layout if the file was parsed.
+    pub layout: Option<Path>,
+    pub message: String,
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuesSpec;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuesState {
+    /// Issues grouped by files.
+    pub issues: BTreeMap<String, Vec<LayoutIssue>>,
+}
+
+impl From<IssuesSpec> for IssuesState {
+    fn from(_spec: IssuesSpec) -> Self {
+        Self {
+            issues: BTreeMap::new(),
+        }
+    }
+}
+
+impl Flow for IssuesState {
+    type Action = ();
+    type Event = IssuesEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            IssuesEvent::Set { file, issues } => {
+                if issues.is_empty() {
+                    self.issues.remove(&file);
+                } else {
+                    self.issues.insert(file, issues);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum IssuesEvent {
+    /// Replaces issues of the file. Empty issues remove the file.
+    Set {
+        file: String,
+        issues: Vec<LayoutIssue>,
+    },
+}
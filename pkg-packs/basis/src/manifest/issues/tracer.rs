@@ -0,0 +1,24 @@
+use super::state::*;
+use derive_more::{Deref, DerefMut};
+use rill_engine::tracers::tracer::Tracer;
+use rill_protocol::flow::core::FlowMode;
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct IssuesTracer {
+    tracer: Tracer<IssuesState>,
+}
+
+impl IssuesTracer {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let path = IssuesSpec::path();
+        let state = IssuesSpec.into();
+        let tracer = Tracer::new(state, path, FlowMode::Realtime);
+        Self { tracer }
+    }
+
+    pub fn set_issues(&self, file: String, issues: Vec<LayoutIssue>) {
+        let msg = IssuesEvent::Set { file, issues };
+        self.tracer.send(msg, None);
+    }
+}
@@ -1,6 +1,18 @@
-use super::components::{Element, Layout};
+use super::components::{Element, Flow, Layout};
+use super::layout::Size;
+use crate::manifest::binder::BindedTracer;
+use crate::manifest::description::PackFlow;
 use crate::manifest::layouts::global::LAYOUTS;
+use crate::manifest::paths::global::PATHS;
 use crate::paths::LayoutPath;
+use rill_protocol::io::provider::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LayoutError {
+    #[error("layout {name} references missing flows: {missing:?}")]
+    MissingFlows { name: Path, missing: Vec<Path> },
+}
 
 impl Layout {
     pub fn new(name: impl Into<LayoutPath>) -> Self {
@@ -10,14 +22,46 @@ impl Layout {
         }
     }
 
+    pub fn with_container(mut self, element: impl Into<Element>) -> Self {
+        self.set_container(element);
+        self
+    }
+
     pub fn set_container(&mut self, element: impl Into<Element>) {
         self.element = element.into();
     }
 
+    /// Returns paths of flows that are not registered yet.
+    pub fn missing_flows(&self) -> Vec<Path> {
+        self.element
+            .flows()
+            .into_iter()
+            .filter(|flow| PATHS.find(&flow.path).is_none())
+            .map(|flow| flow.path.clone())
+            .collect()
+    }
+
+    /// Registers the layout only if all referenced flows exist.
+    pub fn try_register(&self) -> Result<(), LayoutError> {
+        let missing = self.missing_flows();
+        if missing.is_empty() {
+            self.register();
+            Ok(())
+        } else {
+            Err(LayoutError::MissingFlows {
+                name: self.name.clone(),
+                missing,
+            })
+        }
+    }
+
+    /// Registers the layout. Missing flows are reported to the log.
     pub fn register(&self) {
         let name = self.name.clone();
-        let layout = self.clone();
-        LAYOUTS.add_tab(name, layout);
+        for path in self.missing_flows() {
+            log::warn!("Layout {} references missing flow {}", name, path);
+        }
+        LAYOUTS.add_tab(name, self.clone());
     }
 
     pub fn unregister(&self) {
@@ -25,3 +69,59 @@ impl Layout {
         LAYOUTS.remove_tab(name);
     }
 }
+
+impl Flow {
+    /// Creates a flow that references the tracer.
+    /// The size of the card is picked by the layer of the flow.
+    pub fn bind<T: PackFlow>(tracer: &BindedTracer<T>) -> Self {
+        Self {
+            path: tracer.path().clone(),
+            size: Some(Size::of_layer(&T::layer())),
+        }
+    }
+}
+
+impl<T: PackFlow> From<&BindedTracer<T>> for Element {
+    fn from(tracer: &BindedTracer<T>) -> Self {
+        Flow::bind(tracer).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::description::{Layer, PackFlowDescription};
+    use crate::manifest::layouts::components::Column;
+    use rill_protocol::io::provider::StreamType;
+
+    fn add_path(path: &Path) {
+        let description = PackFlowDescription {
+            path: path.clone(),
+            layer: Layer::Visual,
+            stream_type: StreamType::from("test"),
+        };
+        PATHS.add_path(path.clone(), description);
+    }
+
+    #[test]
+    fn test_missing_flows() {
+        let existing = "test.layouts.missing.existing";
+        let absent = "test.layouts.missing.absent";
+        add_path(&existing.parse().unwrap());
+        let column = Column {
+            children: vec![Flow::new(existing).into(), Flow::new(absent).into()],
+        };
+        let layout = Layout::new("test.missing").with_container(column);
+        let missing: Path = absent.parse().unwrap();
+        assert_eq!(layout.missing_flows(), vec![missing.clone()]);
+        match layout.try_register() {
+            Err(LayoutError::MissingFlows { missing: paths, .. }) => {
+                assert_eq!(paths, vec![missing.clone()]);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        add_path(&missing);
+        assert!(layout.missing_flows().is_empty());
+        assert!(layout.try_register().is_ok());
+    }
+}
@@ -1,3 +1,4 @@
+use super::layout::Size;
 use crate::paths::AutoPath;
 use derive_more::From;
 use ordered_float::OrderedFloat;
@@ -34,12 +35,54 @@ pub enum Element {
     Spacer(Spacer),
     Row(Row),
     Column(Column),
+    Grid(Grid),
+    Tabs(Tabs),
+    Section(Section),
+    SizedBox(SizedBox),
 
     // Components
     Text(Text),
     Flow(Flow),
 }
 
+impl Element {
+    /// Collects all flows of the element and its children.
+    pub fn flows(&self) -> Vec<&Flow> {
+        let mut flows = Vec::new();
+        self.collect_flows(&mut flows);
+        flows
+    }
+
+    fn collect_flows<'a>(&'a self, flows: &mut Vec<&'a Flow>) {
+        match self {
+            Self::Empty | Self::Spacer(_) | Self::Text(_) => {}
+            Self::Align(value) => value.child.collect_flows(flows),
+            Self::Center(value) => value.child.collect_flows(flows),
+            Self::Container(value) => value.child.collect_flows(flows),
+            Self::Expanded(value) => value.child.collect_flows(flows),
+            Self::Row(value) => value
+                .children
+                .iter()
+                .for_each(|child| child.collect_flows(flows)),
+            Self::Column(value) => value
+                .children
+                .iter()
+                .for_each(|child| child.collect_flows(flows)),
+            Self::Grid(value) => value
+                .items
+                .iter()
+                .for_each(|item| item.child.collect_flows(flows)),
+            Self::Tabs(value) => value
+                .tabs
+                .iter()
+                .for_each(|tab| tab.child.collect_flows(flows)),
+            Self::Section(value) => value.child.collect_flows(flows),
+            Self::SizedBox(value) => value.child.collect_flows(flows),
+            Self::Flow(value) => flows.push(value),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, From)]
 pub struct Align {
     pub alignment: Alignment,
@@ -116,6 +159,109 @@ impl Column {
     }
 }
 
+/// Places items in cells like the CSS grid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, From)]
+pub struct Grid {
+    pub columns: u16,
+    pub items: Vec<GridItem>,
+}
+
+impl Grid {
+    pub fn new(columns: u16, items: Vec<GridItem>) -> Self {
+        Self { columns, items }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GridItem {
+    pub col_span: u16,
+    pub row_span: u16,
+    pub child: Element,
+}
+
+impl GridItem {
+    pub fn new(child: impl Into<Element>) -> Self {
+        Self::spanned(child, 1, 1)
+    }
+
+    pub fn spanned(child: impl Into<Element>, col_span: u16, row_span: u16) -> Self {
+        Self {
+            col_span,
+            row_span,
+            child: child.into(),
+        }
+    }
+}
+
+/// Shows a single child at a time with a selector of tabs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, From)]
+pub struct Tabs {
+    pub tabs: Vec<Tab>,
+}
+
+impl Tabs {
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self { tabs }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Tab {
+    pub title: String,
+    pub child: Element,
+}
+
+impl Tab {
+    pub fn new(title: impl Into<String>, child: impl Into<Element>) -> Self {
+        Self {
+            title: title.into(),
+            child: child.into(),
+        }
+    }
+}
+
+/// A child with a title that can be collapsed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Section {
+    pub title: String,
+    pub collapsed: bool,
+    pub child: BoxedElement,
+}
+
+impl Section {
+    pub fn new(title: impl Into<String>, child: impl Into<Element>) -> Self {
+        Self {
+            title: title.into(),
+            collapsed: false,
+            child: child.boxed(),
+        }
+    }
+
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+}
+
+/// A box with a fixed size (in pixels).
+/// The size is not restricted in a dimension if it's not set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SizedBox {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub child: BoxedElement,
+}
+
+impl SizedBox {
+    pub fn new(width: Option<u32>, height: Option<u32>, child: impl Into<Element>) -> Self {
+        Self {
+            width,
+            height,
+            child: child.boxed(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Alignment {
     pub x: OrderedFloat<f64>,
@@ -171,12 +317,57 @@ pub enum TextAlign {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, From)]
 pub struct Flow {
     pub path: Path,
+    /// The size of the card. The UI uses the preferred size if not set.
+    #[serde(default)]
+    pub size: Option<Size>,
 }
 
 impl Flow {
     pub fn new(path: impl Into<AutoPath>) -> Self {
         Self {
             path: path.into().into(),
+            size: None,
         }
     }
+
+    pub fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flows_of_containers() {
+        let grid = Grid::new(
+            2,
+            vec![
+                GridItem::spanned(Flow::new("layout.grid.flow.first"), 2, 1),
+                GridItem::new(Flow::new("layout.grid.flow.second")),
+            ],
+        );
+        let tabs = Tabs::new(vec![
+            Tab::new("Grid", grid),
+            Tab::new("Empty", Element::Empty),
+        ]);
+        let section = Section::new("Section", tabs).collapsed(true);
+        let sized = SizedBox::new(Some(100), None, Flow::new("layout.sized.flow.single"));
+        let element: Element = Column::new(vec![section.into(), sized.into()]).into();
+        let paths: Vec<String> = element
+            .flows()
+            .into_iter()
+            .map(|flow| flow.path.to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "layout.grid.flow.first",
+                "layout.grid.flow.second",
+                "layout.sized.flow.single"
+            ]
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::manifest::description::Layer;
 use serde::{Deserialize, Serialize};
 
 /*
@@ -40,3 +41,14 @@ where
         }
     }
 }
+
+impl Size {
+    /// The default size of a card for flows of the `layer`.
+    pub fn of_layer(layer: &Layer) -> Self {
+        match layer {
+            Layer::Visual => (300, 200).into(),
+            Layer::Control => (300, 100).into(),
+            Layer::Transparent => (100, 100).into(),
+        }
+    }
+}
@@ -4,5 +4,6 @@ pub mod binder;
 pub use binder::BindedTracer;
 
 pub mod description;
+pub mod issues;
 pub mod layouts;
 pub mod paths;
@@ -4,10 +4,20 @@ use derive_more::{Deref, DerefMut};
 use rill_engine::tracers::tracer::Tracer;
 use rill_protocol::flow::core::FlowMode;
 use rill_protocol::io::provider::Path;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+type Records = BTreeMap<Path, PackFlowDescription>;
 
 #[derive(Debug, Deref, DerefMut, Clone)]
 pub struct PathsTracer {
+    #[deref]
+    #[deref_mut]
     tracer: Tracer<PathsState>,
+    /// A copy of registered paths to check them locally.
+    records: Arc<Mutex<Records>>,
+    subscribers: Arc<Mutex<Vec<mpsc::UnboundedSender<Path>>>>,
 }
 
 impl PathsTracer {
@@ -16,16 +26,59 @@ impl PathsTracer {
         let path = PathsSpec::path();
         let state = PathsSpec.into();
         let tracer = Tracer::new(state, path, FlowMode::Realtime);
-        Self { tracer }
+        Self {
+            tracer,
+            records: Arc::default(),
+            subscribers: Arc::default(),
+        }
     }
 
     pub fn add_path(&self, path: Path, description: PackFlowDescription) {
+        self.with_records(|records| records.insert(path.clone(), description.clone()));
+        self.notify(&path);
         let msg = PathsEvent::Add { path, description };
         self.tracer.send(msg, None);
     }
 
     pub fn remove_path(&self, path: Path) {
+        self.with_records(|records| records.remove(&path));
+        self.notify(&path);
         let msg = PathsEvent::Remove { path };
         self.tracer.send(msg, None);
     }
+
+    /// Returns the description of a registered flow.
+    pub fn find(&self, path: &Path) -> Option<PackFlowDescription> {
+        self.with_records(|records| records.get(path).cloned())
+    }
+
+    /// Returns a receiver of paths that were added or removed.
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<Path> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        subscribers.push(tx);
+        rx
+    }
+
+    fn notify(&self, path: &Path) {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        subscribers.retain(|tx| tx.send(path.clone()).is_ok());
+    }
+
+    fn with_records<F, R>(&self, func: F) -> R
+    where
+        F: FnOnce(&mut Records) -> R,
+    {
+        let mut records = self
+            .records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        func(&mut records)
+    }
 }
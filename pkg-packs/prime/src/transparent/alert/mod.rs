@@ -7,3 +7,13 @@ pub use state::*;
 pub mod tracer;
 #[cfg(feature = "engine")]
 pub use tracer::*;
+
+#[cfg(feature = "engine")]
+pub mod notification;
+#[cfg(feature = "engine")]
+pub use notification::Notification;
+
+#[cfg(feature = "engine")]
+pub mod rule;
+#[cfg(feature = "engine")]
+pub use rule::*;
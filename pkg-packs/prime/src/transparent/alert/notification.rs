@@ -0,0 +1,32 @@
+use super::state::{RecordKind, Severity};
+use once_cell::sync::Lazy;
+use rill_protocol::io::provider::{Path, Timestamp};
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+
+/// An alert delivered to in-process subscribers (notifiers).
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub path: Path,
+    pub timestamp: Timestamp,
+    pub kind: RecordKind,
+    pub rule: Option<String>,
+    pub severity: Severity,
+    pub text: String,
+}
+
+static SUBSCRIBERS: Lazy<Mutex<Vec<mpsc::UnboundedSender<Notification>>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Returns a receiver of notifications from all `Alert` tracers.
+pub fn subscribe() -> mpsc::UnboundedReceiver<Notification> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut subscribers = SUBSCRIBERS.lock().unwrap_or_else(|err| err.into_inner());
+    subscribers.push(tx);
+    rx
+}
+
+pub(super) fn publish(notification: Notification) {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap_or_else(|err| err.into_inner());
+    subscribers.retain(|tx| tx.send(notification.clone()).is_ok());
+}
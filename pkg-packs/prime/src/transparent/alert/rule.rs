@@ -0,0 +1,304 @@
+use super::state::Severity;
+use super::tracer::Alert;
+use rill_engine::tracers::tracer::Tracer;
+use rill_protocol::flow::core::Flow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How often rules are checked by the recorder of the flow.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Rates are calculated by changes in this window.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Condition {
+    Above(f64),
+    Below(f64),
+    /// Changes per second are above the value.
+    RateAbove(f64),
+    /// Changes per second are below the value.
+    RateBelow(f64),
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Above(value) => write!(f, "above {}", value),
+            Self::Below(value) => write!(f, "below {}", value),
+            Self::RateAbove(value) => write!(f, "rate above {}/s", value),
+            Self::RateBelow(value) => write!(f, "rate below {}/s", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub condition: Condition,
+    /// How long the condition has to be met to fire.
+    pub duration: Duration,
+    pub severity: Severity,
+}
+
+impl AlertRule {
+    pub fn new(condition: Condition) -> Self {
+        Self {
+            condition,
+            duration: Duration::from_secs(0),
+            severity: Severity::Warn,
+        }
+    }
+
+    pub fn above(value: impl Into<f64>) -> Self {
+        Self::new(Condition::Above(value.into()))
+    }
+
+    pub fn below(value: impl Into<f64>) -> Self {
+        Self::new(Condition::Below(value.into()))
+    }
+
+    pub fn rate_above(per_sec: impl Into<f64>) -> Self {
+        Self::new(Condition::RateAbove(per_sec.into()))
+    }
+
+    pub fn rate_below(per_sec: impl Into<f64>) -> Self {
+        Self::new(Condition::RateBelow(per_sec.into()))
+    }
+
+    pub fn for_secs(mut self, secs: u64) -> Self {
+        self.duration = Duration::from_secs(secs);
+        self
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Attaches the rule to the flow. The rule is checked by the recorder
+    /// of the flow against the `value` of the state and sends
+    /// notifications to the `alert`.
+    pub fn attach<T, F>(self, tracer: &Tracer<T>, alert: &Alert, value: F)
+    where
+        T: Flow,
+        F: Fn(&T) -> Option<f64>,
+        F: Send + 'static,
+    {
+        let mut watcher = Watcher::new(tracer.path(), self, Instant::now());
+        let alert = alert.clone();
+        tracer.ticker(TICK_INTERVAL, move |state: &T| {
+            match watcher.check(value(state), Instant::now()) {
+                Some(Transition::Firing { severity, text }) => {
+                    alert.fire(&watcher.id, severity, text);
+                }
+                Some(Transition::Resolved { text }) => {
+                    alert.resolve(&watcher.id, text);
+                }
+                None => {}
+            }
+            None
+        });
+    }
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.condition)?;
+        if !self.duration.is_zero() {
+            write!(f, " for {}s", self.duration.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+/// A change of the rule state that has to be sent to an alert.
+#[derive(Debug, Clone, PartialEq)]
+enum Transition {
+    Firing { severity: Severity, text: String },
+    Resolved { text: String },
+}
+
+#[derive(Debug)]
+struct Watcher {
+    /// Unique id of the rule in the alert.
+    id: String,
+    name: String,
+    rule: AlertRule,
+    created: Instant,
+    value: Option<f64>,
+    changes: VecDeque<(Instant, f64)>,
+    /// When the condition started to be met.
+    since: Option<Instant>,
+    firing: bool,
+}
+
+impl Watcher {
+    fn new(name: impl ToString, rule: AlertRule, now: Instant) -> Self {
+        let name = name.to_string();
+        Self {
+            id: format!("{} {}", name, rule),
+            name,
+            rule,
+            created: now,
+            value: None,
+            changes: VecDeque::new(),
+            since: None,
+            firing: false,
+        }
+    }
+
+    fn observe(&mut self, value: f64, now: Instant) {
+        if let Some(prev) = self.value {
+            self.changes.push_back((now, value - prev));
+        }
+        self.value = Some(value);
+    }
+
+    /// Returns `None` if there is not enough data to calculate it.
+    fn rate(&mut self, now: Instant) -> Option<f64> {
+        while let Some((ts, _)) = self.changes.front() {
+            if now.duration_since(*ts) > RATE_WINDOW {
+                self.changes.pop_front();
+            } else {
+                break;
+            }
+        }
+        if now.duration_since(self.created) < RATE_WINDOW {
+            return None;
+        }
+        let sum: f64 = self.changes.iter().map(|(_, delta)| delta).sum();
+        Some(sum / RATE_WINDOW.as_secs_f64())
+    }
+
+    fn is_met(&mut self, now: Instant) -> Option<bool> {
+        match self.rule.condition {
+            Condition::Above(limit) => self.value.map(|value| value > limit),
+            Condition::Below(limit) => self.value.map(|value| value < limit),
+            Condition::RateAbove(limit) => self.rate(now).map(|rate| rate > limit),
+            Condition::RateBelow(limit) => self.rate(now).map(|rate| rate < limit),
+        }
+    }
+
+    /// Observes the current value and returns a transition
+    /// only if the rule started or stopped firing.
+    fn check(&mut self, value: Option<f64>, now: Instant) -> Option<Transition> {
+        if let Some(value) = value {
+            self.observe(value, now);
+        }
+        if self.is_met(now).unwrap_or_default() {
+            let since = *self.since.get_or_insert(now);
+            if !self.firing && now.duration_since(since) >= self.rule.duration {
+                self.firing = true;
+                let severity = self.rule.severity;
+                let text = format!("{} is {}", self.name, self.rule);
+                return Some(Transition::Firing { severity, text });
+            }
+        } else {
+            self.since = None;
+            if self.firing {
+                self.firing = false;
+                let text = format!("{} is not {}", self.name, self.rule.condition);
+                return Some(Transition::Resolved { text });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(start: Instant, secs: u64) -> Instant {
+        start + Duration::from_secs(secs)
+    }
+
+    fn is_firing(transition: Option<Transition>) -> bool {
+        matches!(transition, Some(Transition::Firing { .. }))
+    }
+
+    fn is_resolved(transition: Option<Transition>) -> bool {
+        matches!(transition, Some(Transition::Resolved { .. }))
+    }
+
+    #[test]
+    fn test_condition_hold() {
+        let start = Instant::now();
+        let rule = AlertRule::above(10).for_secs(30);
+        let mut watcher = Watcher::new("gauge", rule, start);
+        assert_eq!(watcher.check(None, start), None);
+        assert_eq!(watcher.check(Some(20.0), start), None);
+        assert_eq!(watcher.check(Some(20.0), secs(start, 29)), None);
+        // The condition is not met for a moment and the hold is restarted
+        assert_eq!(watcher.check(Some(5.0), secs(start, 30)), None);
+        assert_eq!(watcher.check(Some(20.0), secs(start, 31)), None);
+        assert_eq!(watcher.check(Some(20.0), secs(start, 60)), None);
+        let transition = watcher.check(None, secs(start, 61));
+        assert_eq!(
+            transition,
+            Some(Transition::Firing {
+                severity: Severity::Warn,
+                text: "gauge is above 10 for 30s".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_firing_resolved() {
+        let start = Instant::now();
+        let rule = AlertRule::below(0).severity(Severity::Critical);
+        let mut watcher = Watcher::new("gauge", rule, start);
+        assert_eq!(watcher.id, "gauge below 0");
+        assert!(is_firing(watcher.check(Some(-1.0), start)));
+        assert_eq!(
+            watcher.check(Some(1.0), secs(start, 1)),
+            Some(Transition::Resolved {
+                text: "gauge is not below 0".into(),
+            })
+        );
+        assert!(is_firing(watcher.check(Some(-2.0), secs(start, 2))));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let start = Instant::now();
+        let mut watcher = Watcher::new("gauge", AlertRule::above(10), start);
+        assert!(is_firing(watcher.check(Some(20.0), start)));
+        // Ticks without new values don't repeat notifications
+        for sec in 1..5 {
+            assert_eq!(watcher.check(None, secs(start, sec)), None);
+            assert_eq!(watcher.check(Some(30.0), secs(start, sec)), None);
+        }
+        assert!(is_resolved(watcher.check(Some(0.0), secs(start, 5))));
+        for sec in 6..10 {
+            assert_eq!(watcher.check(Some(0.0), secs(start, sec)), None);
+        }
+    }
+
+    #[test]
+    fn test_rate_below() {
+        let start = Instant::now();
+        let mut watcher = Watcher::new("counter", AlertRule::rate_below(1), start);
+        let mut total = 0.0;
+        // Not enough data to calculate the rate
+        for sec in 0..60 {
+            total += 2.0;
+            assert_eq!(watcher.check(Some(total), secs(start, sec)), None);
+        }
+        // 2 per second
+        assert_eq!(watcher.check(Some(total + 2.0), secs(start, 60)), None);
+        // The counter has stopped
+        let mut fired = None;
+        for sec in 61..=120 {
+            if is_firing(watcher.check(Some(total + 2.0), secs(start, sec))) {
+                fired = Some(sec);
+            }
+        }
+        // The rate drops below 1 when less than 30 changes remain in the window
+        assert_eq!(fired, Some(92));
+        assert!(is_resolved(
+            watcher.check(Some(total + 200.0), secs(start, 121))
+        ));
+    }
+}
@@ -1,19 +1,142 @@
 use rill_protocol::flow::core::Flow;
 use rill_protocol::io::provider::StreamType;
+use rrpack_basis::frames::{Frame, TimedEvent};
 use rrpack_basis::manifest::description::{Layer, PackFlow};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AlertSpec {}
+pub struct AlertSpec {
+    /// The amount of records kept in the log.
+    pub history: u32,
+}
+
+impl Default for AlertSpec {
+    fn default() -> Self {
+        Self { history: 100 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl Default for Severity {
+    fn default() -> Self {
+        Self::Info
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Critical => "critical",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordKind {
+    Notify,
+    Firing,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRecord {
+    /// Sequential id of the record used to acknowledge it.
+    pub id: u64,
+    pub kind: RecordKind,
+    pub rule: Option<String>,
+    pub severity: Severity,
+    pub text: String,
+    pub acked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiringRule {
+    pub severity: Severity,
+    pub text: String,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertState {
-    // TODO: Decide: persistent or not?
+    pub spec: AlertSpec,
+    /// Firing rules by their names.
+    #[serde(with = "vectorize")]
+    pub firing: BTreeMap<String, FiringRule>,
+    /// The latest records. They are kept in memory only
+    /// and are lost when the app restarts.
+    pub history: Frame<TimedEvent<AlertRecord>>,
+    next_id: u64,
 }
 
 impl From<AlertSpec> for AlertState {
-    fn from(_spec: AlertSpec) -> Self {
-        Self {}
+    fn from(spec: AlertSpec) -> Self {
+        let history = Frame::new(spec.history);
+        Self {
+            spec,
+            firing: BTreeMap::new(),
+            history,
+            next_id: 0,
+        }
+    }
+}
+
+impl AlertState {
+    /// Records that were not acknowledged yet.
+    pub fn unacked(&self) -> impl DoubleEndedIterator<Item = &TimedEvent<AlertRecord>> {
+        self.history.iter().filter(|record| !record.event.acked)
+    }
+
+    /// The highest severity of firing rules.
+    pub fn severity(&self) -> Option<Severity> {
+        self.firing.values().map(|rule| rule.severity).max()
+    }
+
+    fn record(&mut self, event: TimedEvent<AlertEvent>) {
+        let (kind, rule, severity, text) = match event.event {
+            AlertEvent::Notify { severity, text } => (RecordKind::Notify, None, severity, text),
+            AlertEvent::Firing {
+                rule,
+                severity,
+                text,
+            } => (RecordKind::Firing, Some(rule), severity, text),
+            AlertEvent::Resolved { rule, text } => {
+                let severity = self
+                    .firing
+                    .remove(&rule)
+                    .map(|rule| rule.severity)
+                    .unwrap_or_default();
+                (RecordKind::Resolved, Some(rule), severity, text)
+            }
+            AlertEvent::Ack { .. } => {
+                return;
+            }
+        };
+        let record = AlertRecord {
+            id: self.next_id,
+            kind,
+            rule,
+            severity,
+            text,
+            acked: false,
+        };
+        self.next_id += 1;
+        let record = TimedEvent {
+            timestamp: event.timestamp,
+            event: record,
+        };
+        self.history.insert_pop(record);
     }
 }
 
@@ -25,18 +148,115 @@ impl PackFlow for AlertState {
 
 impl Flow for AlertState {
     type Action = AlertAction;
-    type Event = AlertEvent;
+    type Event = TimedEvent<AlertEvent>;
 
     fn stream_type() -> StreamType {
         StreamType::from(module_path!())
     }
 
-    fn apply(&mut self, _event: Self::Event) {}
+    fn apply(&mut self, event: Self::Event) {
+        match &event.event {
+            AlertEvent::Notify { .. } | AlertEvent::Resolved { .. } => {}
+            AlertEvent::Firing {
+                rule,
+                severity,
+                text,
+            } => {
+                let firing = FiringRule {
+                    severity: *severity,
+                    text: text.clone(),
+                };
+                self.firing.insert(rule.clone(), firing);
+            }
+            AlertEvent::Ack { id } => {
+                let id = *id;
+                self.history
+                    .iter_mut()
+                    .filter(|record| id.map(|id| id == record.event.id).unwrap_or(true))
+                    .for_each(|record| record.event.acked = true);
+                return;
+            }
+        }
+        self.record(event);
+    }
 }
 
-pub type AlertAction = ();
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AlertAction {
+    /// Acknowledges a record by its id or all records if it's not set.
+    Ack { id: Option<u64> },
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlertEvent {
-    Notify { text: String },
+    Notify {
+        severity: Severity,
+        text: String,
+    },
+    /// A condition of the rule is met.
+    Firing {
+        rule: String,
+        severity: Severity,
+        text: String,
+    },
+    /// A condition of the rule is not met anymore.
+    Resolved {
+        rule: String,
+        text: String,
+    },
+    Ack {
+        id: Option<u64>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn apply(state: &mut AlertState, event: AlertEvent) {
+        let event = TimedEvent {
+            timestamp: Timestamp(0),
+            event,
+        };
+        state.apply(event);
+    }
+
+    #[test]
+    fn test_alert_history() {
+        let mut state = AlertState::from(AlertSpec { history: 2 });
+        apply(
+            &mut state,
+            AlertEvent::Firing {
+                rule: "cpu".into(),
+                severity: Severity::Critical,
+                text: "cpu is above 90".into(),
+            },
+        );
+        assert_eq!(state.severity(), Some(Severity::Critical));
+        apply(
+            &mut state,
+            AlertEvent::Resolved {
+                rule: "cpu".into(),
+                text: "cpu is not above 90".into(),
+            },
+        );
+        assert_eq!(state.severity(), None);
+        let resolved = &state.history.back().unwrap().event;
+        assert_eq!(resolved.kind, RecordKind::Resolved);
+        assert_eq!(resolved.severity, Severity::Critical);
+
+        apply(&mut state, AlertEvent::Ack { id: Some(1) });
+        assert_eq!(state.unacked().count(), 1);
+        apply(
+            &mut state,
+            AlertEvent::Notify {
+                severity: Severity::Info,
+                text: "restarted".into(),
+            },
+        );
+        apply(&mut state, AlertEvent::Ack { id: None });
+        assert_eq!(state.unacked().count(), 0);
+        assert_eq!(state.history.back().unwrap().event.id, 2);
+    }
 }
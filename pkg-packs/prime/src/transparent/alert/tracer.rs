@@ -1,15 +1,21 @@
+use super::notification::{self, Notification};
 use super::state::*;
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::{timed, TimedEvent};
 use rrpack_basis::{AutoPath, BindedTracer};
 
 #[derive(TracerOpts, Clone, Default)]
-pub struct AlertOpts {}
+pub struct AlertOpts {
+    pub history: Option<u32>,
+}
 
 impl From<AlertOpts> for AlertSpec {
-    fn from(_opts: AlertOpts) -> Self {
-        Self {}
+    fn from(opts: AlertOpts) -> Self {
+        Self {
+            history: opts.history.unwrap_or_else(|| AlertSpec::default().history),
+        }
     }
 }
 
@@ -21,13 +27,89 @@ pub struct Alert {
 impl Alert {
     pub fn new(auto_path: impl Into<AutoPath>, spec: impl Into<AlertSpec>) -> Self {
         let tracer = BindedTracer::new(auto_path.into(), FlowMode::Realtime, spec.into());
+        // Records are acknowledged from the dashboard without callbacks
+        tracer.reactor(|_: &AlertState, action: &AlertAction| match action {
+            AlertAction::Ack { id } => timed(AlertEvent::Ack { id: *id }),
+        });
         Self { tracer }
     }
 
     pub fn notify(&self, reason: impl Into<String>) {
+        self.notify_with(Severity::Info, reason);
+    }
+
+    pub fn warn(&self, reason: impl Into<String>) {
+        self.notify_with(Severity::Warn, reason);
+    }
+
+    pub fn critical(&self, reason: impl Into<String>) {
+        self.notify_with(Severity::Critical, reason);
+    }
+
+    pub fn notify_with(&self, severity: Severity, reason: impl Into<String>) {
         let msg = AlertEvent::Notify {
+            severity,
             text: reason.into(),
         };
-        self.tracer.send(msg, None);
+        self.send_event(msg);
+    }
+
+    /// Starts firing of the rule.
+    pub fn fire(&self, rule: impl ToString, severity: Severity, text: impl Into<String>) {
+        let msg = AlertEvent::Firing {
+            rule: rule.to_string(),
+            severity,
+            text: text.into(),
+        };
+        self.send_event(msg);
+    }
+
+    pub fn resolve(&self, rule: impl ToString, text: impl Into<String>) {
+        let msg = AlertEvent::Resolved {
+            rule: rule.to_string(),
+            text: text.into(),
+        };
+        self.send_event(msg);
+    }
+
+    /// Marks the record (or all records if `id` is not set) as acknowledged.
+    /// `AlertAction::Ack` from the dashboard is applied automatically.
+    pub fn ack(&self, id: Option<u64>) {
+        let msg = AlertEvent::Ack { id };
+        self.send_event(msg);
+    }
+
+    fn send_event(&self, event: AlertEvent) {
+        if let Some(event) = timed(event) {
+            self.publish(&event);
+            self.tracer.send(event, None);
+        }
+    }
+
+    fn publish(&self, event: &TimedEvent<AlertEvent>) {
+        let (kind, rule, severity, text) = match &event.event {
+            AlertEvent::Notify { severity, text } => (RecordKind::Notify, None, *severity, text),
+            AlertEvent::Firing {
+                rule,
+                severity,
+                text,
+            } => (RecordKind::Firing, Some(rule), *severity, text),
+            // The severity is known by notifiers that tracked firing of the rule
+            AlertEvent::Resolved { rule, text } => {
+                (RecordKind::Resolved, Some(rule), Severity::Info, text)
+            }
+            AlertEvent::Ack { .. } => {
+                return;
+            }
+        };
+        let notification = Notification {
+            path: self.tracer.path().clone(),
+            timestamp: event.timestamp,
+            kind,
+            rule: rule.cloned(),
+            severity,
+            text: text.clone(),
+        };
+        notification::publish(notification);
     }
 }
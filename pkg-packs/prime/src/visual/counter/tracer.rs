@@ -1,4 +1,5 @@
 use super::state::*;
+use crate::transparent::alert::{Alert, AlertRule};
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
@@ -28,6 +29,13 @@ impl Counter {
         Self { tracer }
     }
 
+    /// Attaches a rule that sends notifications to the `alert`.
+    pub fn add_alert(&self, alert: &Alert, rule: AlertRule) {
+        rule.attach(&self.tracer, alert, |state: &CounterState| {
+            Some(state.total as f64)
+        });
+    }
+
     pub fn inc(&self, delta: impl Into<i64>) {
         let msg = CounterEvent::Inc {
             delta: delta.into(),
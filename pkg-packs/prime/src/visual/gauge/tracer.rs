@@ -1,5 +1,6 @@
 use super::state::*;
 use crate::range::{Bound, Range};
+use crate::transparent::alert::{Alert, AlertRule};
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
@@ -35,6 +36,11 @@ impl Gauge {
         Self { tracer }
     }
 
+    /// Attaches a rule that sends notifications to the `alert`.
+    pub fn add_alert(&self, alert: &Alert, rule: AlertRule) {
+        rule.attach(&self.tracer, alert, |state: &GaugeState| state.value);
+    }
+
     pub fn set(&self, value: impl Into<f64>) {
         let msg = GaugeEvent::Set {
             value: value.into(),
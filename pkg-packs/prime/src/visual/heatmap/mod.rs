@@ -0,0 +1,9 @@
+//! Matrix of values colored by a scale.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
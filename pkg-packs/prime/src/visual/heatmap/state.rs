@@ -0,0 +1,238 @@
+use crate::range::{Label, Range};
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::StreamType;
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HeatmapSpec {
+    /// Labels of rows in the order to draw.
+    pub rows: Vec<String>,
+    /// Labels of columns in the order to draw.
+    pub cols: Vec<String>,
+    /// Bounds of the color scale.
+    pub range: Range,
+    pub label: Label,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapState {
+    pub spec: HeatmapSpec,
+    /// Rows from the spec followed by rows added with events.
+    pub rows: Vec<String>,
+    /// Columns from the spec followed by columns added with events.
+    pub cols: Vec<String>,
+    /// Values by labels of rows and columns.
+    pub cells: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl From<HeatmapSpec> for HeatmapState {
+    fn from(spec: HeatmapSpec) -> Self {
+        let rows = spec.rows.clone();
+        let cols = spec.cols.clone();
+        Self {
+            spec,
+            rows,
+            cols,
+            cells: BTreeMap::new(),
+        }
+    }
+}
+
+impl HeatmapState {
+    pub fn get(&self, row: &str, col: &str) -> Option<f64> {
+        self.cells.get(row)?.get(col).copied()
+    }
+
+    /// Bounds of the color scale extended by the actual values.
+    pub fn bounds(&self) -> Bounds {
+        let mut values = self.cells.values().flat_map(BTreeMap::values).peekable();
+        let (mut min, mut max) = if values.peek().is_some() {
+            (f64::MAX, f64::MIN)
+        } else {
+            (0.0, 0.0)
+        };
+        for value in values {
+            min = min.min(*value);
+            max = max.max(*value);
+        }
+        Bounds {
+            min: self.spec.range.min.min(min),
+            max: self.spec.range.max.max(max),
+        }
+    }
+
+    fn cell_mut(&mut self, row: String, col: String) -> &mut f64 {
+        if !self.rows.contains(&row) {
+            self.rows.push(row.clone());
+        }
+        if !self.cols.contains(&col) {
+            self.cols.push(col.clone());
+        }
+        self.cells.entry(row).or_default().entry(col).or_default()
+    }
+}
+
+/// Bounds of the color scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Bounds {
+    /// Returns a position of the value on the color scale in range `[0;1]`.
+    pub fn scale(&self, value: f64) -> f64 {
+        let diff = self.max - self.min;
+        if diff > 0.0 {
+            ((value - self.min) / diff).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl PackFlow for HeatmapState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for HeatmapState {
+    type Action = ();
+    type Event = HeatmapEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            HeatmapEvent::Set { row, col, value } => {
+                *self.cell_mut(row, col) = value;
+            }
+            HeatmapEvent::Add { row, col, delta } => {
+                *self.cell_mut(row, col) += delta;
+            }
+            HeatmapEvent::Clear => {
+                self.cells.clear();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HeatmapEvent {
+    Set {
+        row: String,
+        col: String,
+        value: f64,
+    },
+    Add {
+        row: String,
+        col: String,
+        delta: f64,
+    },
+    Clear,
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+    use crate::range::Bound;
+
+    fn set(state: &mut HeatmapState, row: &str, col: &str, value: f64) {
+        let event = HeatmapEvent::Set {
+            row: row.into(),
+            col: col.into(),
+            value,
+        };
+        state.apply(event);
+    }
+
+    #[test]
+    fn test_bounds_without_range() {
+        let mut state = HeatmapState::from(HeatmapSpec::default());
+        assert_eq!(state.bounds(), Bounds { min: 0.0, max: 0.0 });
+        assert_eq!(state.bounds().scale(10.0), 0.0);
+        set(&mut state, "a", "x", 5.0);
+        set(&mut state, "b", "y", 15.0);
+        let bounds = state.bounds();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: 5.0,
+                max: 15.0
+            }
+        );
+        assert_eq!(bounds.scale(7.0), 0.2);
+        assert_eq!(bounds.scale(-1.0), 0.0);
+        assert_eq!(bounds.scale(30.0), 1.0);
+        assert_eq!(state.get("a", "x"), Some(5.0));
+        assert_eq!(state.get("a", "y"), None);
+    }
+
+    #[test]
+    fn test_bounds_with_range() {
+        let spec = HeatmapSpec {
+            range: Range::new(-10.0, 10.0),
+            ..HeatmapSpec::default()
+        };
+        let mut state = HeatmapState::from(spec);
+        assert_eq!(
+            state.bounds(),
+            Bounds {
+                min: -10.0,
+                max: 10.0
+            }
+        );
+        set(&mut state, "a", "x", 5.0);
+        set(&mut state, "a", "y", 30.0);
+        let bounds = state.bounds();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: -10.0,
+                max: 10.0
+            }
+        );
+        assert_eq!(bounds.scale(0.0), 0.5);
+        assert_eq!(bounds.scale(30.0), 1.0);
+    }
+
+    #[test]
+    fn test_bounds_with_loose_range() {
+        let loose = |value| Bound::Accurate {
+            value,
+            strict: false,
+        };
+        let spec = HeatmapSpec {
+            range: Range {
+                min: loose(0.0),
+                max: loose(10.0),
+            },
+            ..HeatmapSpec::default()
+        };
+        let mut state = HeatmapState::from(spec);
+        set(&mut state, "a", "x", 5.0);
+        assert_eq!(
+            state.bounds(),
+            Bounds {
+                min: 0.0,
+                max: 10.0
+            }
+        );
+        set(&mut state, "a", "y", 30.0);
+        let bounds = state.bounds();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: 0.0,
+                max: 30.0
+            }
+        );
+        assert_eq!(bounds.scale(15.0), 0.5);
+    }
+}
@@ -0,0 +1,73 @@
+use super::state::*;
+use crate::range::{Bound, Label, Range};
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct HeatmapOpts {
+    pub rows: Vec<String>,
+    pub cols: Vec<String>,
+
+    pub suffix: Option<String>,
+    pub divisor: Option<f64>,
+
+    pub min: Option<f64>,
+    pub lower: Option<bool>,
+    pub max: Option<f64>,
+    pub higher: Option<bool>,
+}
+
+impl From<HeatmapOpts> for HeatmapSpec {
+    fn from(opts: HeatmapOpts) -> Self {
+        Self {
+            rows: opts.rows,
+            cols: opts.cols,
+            label: Label::from_options(opts.suffix, opts.divisor),
+            range: Range {
+                min: Bound::from_options(opts.min, opts.lower),
+                max: Bound::from_options(opts.max, opts.higher),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Heatmap {
+    tracer: BindedTracer<HeatmapState>,
+}
+
+impl Heatmap {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<HeatmapSpec>,
+    ) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    pub fn set(&self, row: impl ToString, col: impl ToString, value: impl Into<f64>) {
+        let msg = HeatmapEvent::Set {
+            row: row.to_string(),
+            col: col.to_string(),
+            value: value.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    /// Adds the delta to the value of the cell.
+    pub fn add(&self, row: impl ToString, col: impl ToString, delta: impl Into<f64>) {
+        let msg = HeatmapEvent::Add {
+            row: row.to_string(),
+            col: col.to_string(),
+            delta: delta.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    pub fn clear(&self) {
+        self.tracer.send(HeatmapEvent::Clear, None);
+    }
+}
@@ -2,13 +2,33 @@ use ordered_float::OrderedFloat;
 use rill_protocol::flow::core::Flow;
 use rill_protocol::io::provider::StreamType;
 use rill_protocol::range::Pct;
+use rrpack_basis::frames::{TimedEvent, TimedFrame};
 use rrpack_basis::manifest::description::{Layer, PackFlow};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+/// The max amount of values kept in the sliding window.
+pub const WINDOW_LIMIT: u32 = 1_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramSpec {
     pub levels: Vec<f64>,
+    /// The size of a sliding window in seconds.
+    /// The window keeps `WINDOW_LIMIT` values at most.
+    /// The histogram accumulates values forever if it's not set.
+    pub window: Option<u32>,
+    /// Quantiles (in range `[0;1]`) to calculate.
+    pub quantiles: Vec<f64>,
+}
+
+impl Default for HistogramSpec {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            window: None,
+            quantiles: vec![0.5, 0.9, 0.99],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,19 +49,24 @@ impl Stat {
         self.count += 1;
     }
 
-    /* TODO: Use later for sliding window
     fn del(&mut self, value: f64) {
         self.sum -= value;
-        self.count -= 1;
+        self.count = self.count.saturating_sub(1);
     }
-    */
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramState {
+    pub spec: HistogramSpec,
     #[serde(with = "vectorize")]
     pub buckets: BTreeMap<OrderedFloat<f64>, Stat>,
     pub total: Stat,
+    /// The lowest observed value.
+    pub min: Option<f64>,
+    /// The highest observed value.
+    pub max: Option<f64>,
+    /// Values of the sliding window.
+    pub frame: Option<TimedFrame<f64>>,
 }
 
 impl From<HistogramSpec> for HistogramState {
@@ -53,9 +78,16 @@ impl From<HistogramSpec> for HistogramState {
             .collect();
         let inf_level = OrderedFloat::from(f64::INFINITY);
         buckets.entry(inf_level).or_default();
+        let frame = spec
+            .window
+            .map(|secs| TimedFrame::with_limits(Some(secs as i64 * 1_000), Some(WINDOW_LIMIT)));
         Self {
+            spec,
             buckets,
             total: Stat::default(),
+            min: None,
+            max: None,
+            frame,
         }
     }
 }
@@ -69,6 +101,73 @@ impl HistogramState {
             pct: Pct::from_div(stat.sum, total),
         })
     }
+
+    /// Returns pairs of quantiles from the spec and their values.
+    pub fn quantiles(&self) -> impl Iterator<Item = (f64, Option<f64>)> + '_ {
+        self.spec
+            .quantiles
+            .iter()
+            .map(move |q| (*q, self.quantile(*q)))
+    }
+
+    /// Calculates a quantile.
+    ///
+    /// The value is interpolated by buckets within
+    /// the range of observed values.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.total.count == 0 {
+            return None;
+        }
+        let (min, max) = self.min.zip(self.max)?;
+        let rank = self.total.count as f64 * q.clamp(0.0, 1.0);
+        let mut lower = f64::NEG_INFINITY;
+        let mut passed = 0.0;
+        for (level, stat) in &self.buckets {
+            let upper = level.into_inner();
+            let count = stat.count as f64;
+            if stat.count > 0 && passed + count >= rank {
+                let lower = lower.max(min);
+                let upper = upper.min(max);
+                return Some(lower + (upper - lower) * (rank - passed) / count);
+            }
+            passed += count;
+            lower = upper;
+        }
+        None
+    }
+
+    fn bucket_mut(&mut self, value: f64) -> Option<&mut Stat> {
+        let expected = OrderedFloat::from(value);
+        self.buckets
+            .iter_mut()
+            .find(|(level, _)| &expected <= *level)
+            .map(|(_, stat)| stat)
+    }
+
+    fn add(&mut self, value: f64) {
+        self.total.add(value);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        if let Some(stat) = self.bucket_mut(value) {
+            stat.add(value);
+        }
+    }
+
+    fn del(&mut self, value: f64) {
+        self.total.del(value);
+        if let Some(stat) = self.bucket_mut(value) {
+            stat.del(value);
+        }
+    }
+
+    /// Finds bounds of values of the sliding window.
+    fn update_bounds(&mut self) {
+        if let Some(frame) = self.frame.as_ref() {
+            let values = frame.iter().map(|item| item.event);
+            self.min = values.clone().reduce(f64::min);
+            self.max = values.reduce(f64::max);
+        }
+    }
 }
 
 impl PackFlow for HistogramState {
@@ -87,14 +186,20 @@ impl Flow for HistogramState {
 
     fn apply(&mut self, event: Self::Event) {
         match event {
-            HistogramEvent::Add(amount) => {
-                self.total.add(amount);
-                let expected = OrderedFloat::from(amount);
-                for (level, stat) in &mut self.buckets {
-                    if &expected <= level {
-                        stat.add(amount);
-                        break;
-                    }
+            HistogramEvent::Add(value) => {
+                self.add(value.event);
+                let expired = self
+                    .frame
+                    .as_mut()
+                    .map(|frame| frame.insert_pop(value))
+                    .unwrap_or_default();
+                let mut bound_expired = false;
+                for item in expired {
+                    bound_expired |= Some(item.event) == self.min || Some(item.event) == self.max;
+                    self.del(item.event);
+                }
+                if bound_expired {
+                    self.update_bounds();
                 }
             }
         }
@@ -109,5 +214,80 @@ pub struct Bar {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum HistogramEvent {
-    Add(f64),
+    Add(TimedEvent<f64>),
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn add(state: &mut HistogramState, ts: i64, value: f64) {
+        let event = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: value,
+        };
+        state.apply(HistogramEvent::Add(event));
+    }
+
+    #[test]
+    fn test_interpolated_quantile() {
+        let spec = HistogramSpec {
+            levels: vec![10.0, 20.0],
+            ..HistogramSpec::default()
+        };
+        let mut state = HistogramState::from(spec);
+        assert_eq!(state.quantile(0.5), None);
+        for value in [5.0, 5.0, 15.0, 15.0] {
+            add(&mut state, 0, value);
+        }
+        // Values of buckets are placed between the observed bounds
+        assert_eq!(state.quantile(0.0), Some(5.0));
+        assert_eq!(state.quantile(0.5), Some(10.0));
+        assert_eq!(state.quantile(0.75), Some(12.5));
+        assert_eq!(state.quantile(1.0), Some(15.0));
+        add(&mut state, 0, 100.0);
+        assert_eq!(state.quantile(1.0), Some(100.0));
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let spec = HistogramSpec {
+            levels: vec![10.0],
+            window: Some(1),
+            ..HistogramSpec::default()
+        };
+        let mut state = HistogramState::from(spec);
+        add(&mut state, 0, 1.0);
+        add(&mut state, 500, 2.0);
+        add(&mut state, 900, 3.0);
+        assert_eq!(state.total.count, 3);
+        assert_eq!(state.quantile(0.5), Some(2.0));
+        add(&mut state, 1_500, 20.0);
+        assert_eq!(state.total.count, 2);
+        assert_eq!(state.total.sum, 23.0);
+        assert_eq!((state.min, state.max), (Some(3.0), Some(20.0)));
+        assert_eq!(state.quantile(0.0), Some(3.0));
+        assert_eq!(state.quantile(1.0), Some(20.0));
+    }
+
+    #[test]
+    fn test_window_limit() {
+        let spec = HistogramSpec {
+            levels: vec![10.0],
+            window: Some(60),
+            ..HistogramSpec::default()
+        };
+        let mut state = HistogramState::from(spec);
+        let total = WINDOW_LIMIT as i64 + 10;
+        for ts in 0..total {
+            add(&mut state, ts, ts as f64);
+        }
+        let frame = state.frame.as_ref().unwrap();
+        assert_eq!(frame.len(), WINDOW_LIMIT as usize);
+        assert_eq!(state.total.count, WINDOW_LIMIT as u64);
+        assert_eq!(state.min, Some(10.0));
+        assert_eq!(state.max, Some((total - 1) as f64));
+    }
 }
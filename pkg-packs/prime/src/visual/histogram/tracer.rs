@@ -2,17 +2,28 @@ use super::state::*;
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
 use rrpack_basis::{AutoPath, BindedTracer};
 
 #[derive(TracerOpts, Clone, Default)]
 pub struct HistogramOpts {
     pub levels: Vec<f64>,
+    pub window: Option<u32>,
+    pub quantiles: Vec<f64>,
 }
 
 impl From<HistogramOpts> for HistogramSpec {
     fn from(opts: HistogramOpts) -> Self {
+        let default = HistogramSpec::default();
+        let quantiles = if opts.quantiles.is_empty() {
+            default.quantiles
+        } else {
+            opts.quantiles
+        };
         Self {
             levels: opts.levels,
+            window: opts.window,
+            quantiles,
         }
     }
 }
@@ -33,7 +44,9 @@ impl Histogram {
     }
 
     pub fn add(&self, value: impl Into<f64>) {
-        let msg = HistogramEvent::Add(value.into());
-        self.tracer.send(msg, None);
+        if let Some(value) = timed(value.into()) {
+            let msg = HistogramEvent::Add(value);
+            self.tracer.send(msg, None);
+        }
     }
 }
@@ -0,0 +1,296 @@
+use super::tracer::{LiveTail, LiveTailOpts};
+use crate::transparent::alert::{Alert, Severity};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::AutoPath;
+use std::cell::Cell;
+
+/// Records of these crates are skipped to avoid feedback loops,
+/// since tracers produce logs themselves.
+const IGNORED_TARGETS: &[&str] = &[
+    "meio",
+    "rate_config",
+    "rate_core",
+    "rill_client",
+    "rill_config",
+    "rill_engine",
+    "rill_protocol",
+    "rrpack_basis",
+    "rrpack_prime",
+];
+
+thread_local! {
+    static FORWARDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Checks the `target` is the `module` or its submodule.
+fn in_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .map(|rest| rest.is_empty() || rest.starts_with("::"))
+        .unwrap_or_default()
+}
+
+/// Marks the thread as forwarding a record until dropped.
+struct ForwardingGuard;
+
+impl ForwardingGuard {
+    /// Returns `None` if the thread already forwards a record.
+    fn enter() -> Option<Self> {
+        if FORWARDING.with(|flag| flag.replace(true)) {
+            None
+        } else {
+            Some(Self)
+        }
+    }
+}
+
+impl Drop for ForwardingGuard {
+    fn drop(&mut self) {
+        FORWARDING.with(|flag| flag.set(false));
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+    tail: LiveTail,
+    level: LevelFilter,
+    /// A module of targets. All targets are accepted if it's not set.
+    target: Option<String>,
+}
+
+impl Route {
+    fn accepts(&self, level: Level, target: &str) -> bool {
+        level <= self.level
+            && self
+                .target
+                .as_ref()
+                .map(|module| in_module(target, module))
+                .unwrap_or(true)
+    }
+}
+
+/// Forwards `log` records (and `tracing` events) to `LiveTail` tracers.
+#[derive(Debug, Clone, Default)]
+pub struct LogBridge {
+    routes: Vec<Route>,
+    alert: Option<(Alert, Level)>,
+}
+
+impl LogBridge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bridge with a single `LiveTail` tracer.
+    pub fn with_tail(auto_path: impl Into<AutoPath>, level: LevelFilter) -> Self {
+        let tail = LiveTail::new(auto_path, FlowMode::Realtime, LiveTailOpts::default());
+        Self::new().route(tail, level)
+    }
+
+    /// Forwards records with the `level` or higher to the `tail`.
+    pub fn route(mut self, tail: LiveTail, level: LevelFilter) -> Self {
+        let route = Route {
+            tail,
+            level,
+            target: None,
+        };
+        self.routes.push(route);
+        self
+    }
+
+    /// Forwards records of the `target` module and its submodules only.
+    pub fn route_target(
+        mut self,
+        tail: LiveTail,
+        level: LevelFilter,
+        target: impl Into<String>,
+    ) -> Self {
+        let route = Route {
+            tail,
+            level,
+            target: Some(target.into()),
+        };
+        self.routes.push(route);
+        self
+    }
+
+    /// Sends records with the `level` or higher to the `alert` as well.
+    pub fn alert(mut self, alert: Alert, level: Level) -> Self {
+        self.alert = Some((alert, level));
+        self
+    }
+
+    /// Installs the bridge as the global logger.
+    pub fn install(self) -> Result<(), SetLoggerError> {
+        let max_level = self.max_level();
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        let alert_level = self
+            .alert
+            .as_ref()
+            .map(|(_, level)| level.to_level_filter())
+            .unwrap_or(LevelFilter::Off);
+        self.routes
+            .iter()
+            .map(|route| route.level)
+            .fold(alert_level, Ord::max)
+    }
+
+    fn is_enabled(&self, level: Level, target: &str) -> bool {
+        let ignored = IGNORED_TARGETS
+            .iter()
+            .any(|module| in_module(target, module));
+        !ignored
+            && (self.routes.iter().any(|route| route.accepts(level, target))
+                || self
+                    .alert
+                    .as_ref()
+                    .map(|(_, min)| level <= *min)
+                    .unwrap_or_default())
+    }
+
+    fn forward(&self, level: Level, target: &str, content: String) {
+        // Logs produced while forwarding are skipped
+        let _guard = match ForwardingGuard::enter() {
+            Some(guard) => guard,
+            None => return,
+        };
+        for route in &self.routes {
+            if route.accepts(level, target) {
+                route
+                    .tail
+                    .log_now(target, level.to_string(), content.clone());
+            }
+        }
+        if let Some((alert, min)) = self.alert.as_ref() {
+            if level <= *min {
+                let severity = match level {
+                    Level::Error => Severity::Critical,
+                    Level::Warn => Severity::Warn,
+                    _ => Severity::Info,
+                };
+                alert.notify_with(severity, format!("{}: {}", target, content));
+            }
+        }
+    }
+}
+
+impl Log for LogBridge {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.is_enabled(metadata.level(), metadata.target())
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.is_enabled(record.level(), record.target()) {
+            self.forward(record.level(), record.target(), record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(feature = "tracing-layer")]
+mod layer {
+    use super::LogBridge;
+    use log::Level;
+    use std::fmt::{self, Write};
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context, Layer};
+
+    /// Collects the message and other fields of an event.
+    #[derive(Default)]
+    struct Message {
+        text: String,
+    }
+
+    impl Visit for Message {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            if field.name() == "message" {
+                write!(self.text, "{:?}", value).ok();
+            } else {
+                write!(self.text, "{}={:?}", field.name(), value).ok();
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for LogBridge {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let metadata = event.metadata();
+            let level = match *metadata.level() {
+                tracing::Level::ERROR => Level::Error,
+                tracing::Level::WARN => Level::Warn,
+                tracing::Level::INFO => Level::Info,
+                tracing::Level::DEBUG => Level::Debug,
+                tracing::Level::TRACE => Level::Trace,
+            };
+            if self.is_enabled(level, metadata.target()) {
+                let mut message = Message::default();
+                event.record(&mut message);
+                self.forward(level, metadata.target(), message.text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transparent::alert::AlertSpec;
+
+    fn tail(name: &str) -> LiveTail {
+        let path = format!("app.bridge.tail.{}", name);
+        LiveTail::new(path.as_str(), FlowMode::Realtime, LiveTailOpts::default())
+    }
+
+    #[test]
+    fn test_route_filter() {
+        let bridge = LogBridge::new().route_target(tail("db"), LevelFilter::Info, "app::db");
+        assert!(bridge.is_enabled(Level::Info, "app::db"));
+        assert!(bridge.is_enabled(Level::Error, "app::db::pool"));
+        assert!(!bridge.is_enabled(Level::Debug, "app::db"));
+        assert!(!bridge.is_enabled(Level::Info, "app::dbg"));
+        assert!(!bridge.is_enabled(Level::Info, "app"));
+    }
+
+    #[test]
+    fn test_max_level() {
+        assert_eq!(LogBridge::new().max_level(), LevelFilter::Off);
+        let bridge = LogBridge::new().route(tail("info"), LevelFilter::Info);
+        assert_eq!(bridge.max_level(), LevelFilter::Info);
+        let bridge = bridge.route(tail("warn"), LevelFilter::Warn);
+        assert_eq!(bridge.max_level(), LevelFilter::Info);
+        let alert = Alert::new("app.bridge.alert.debug", AlertSpec::default());
+        let bridge = bridge.alert(alert, Level::Debug);
+        assert_eq!(bridge.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_ignored_targets() {
+        let bridge = LogBridge::new().route(tail("all"), LevelFilter::Trace);
+        assert!(!bridge.is_enabled(Level::Error, "meio"));
+        assert!(!bridge.is_enabled(Level::Error, "rill_engine::tracers"));
+        assert!(!bridge.is_enabled(Level::Error, "rrpack_prime"));
+        assert!(bridge.is_enabled(Level::Error, "meiosis"));
+        assert!(bridge.is_enabled(Level::Error, "rill_ui"));
+        assert!(bridge.is_enabled(Level::Error, "rate_limiter::bucket"));
+    }
+
+    #[test]
+    fn test_forwarding_guard() {
+        let guard = ForwardingGuard::enter();
+        assert!(guard.is_some());
+        assert!(ForwardingGuard::enter().is_none());
+        drop(guard);
+        let guard = ForwardingGuard::enter();
+        assert!(guard.is_some());
+    }
+}
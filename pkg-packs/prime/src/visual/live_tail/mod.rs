@@ -7,3 +7,8 @@ pub use state::*;
 pub mod tracer;
 #[cfg(feature = "engine")]
 pub use tracer::*;
+
+#[cfg(feature = "engine")]
+pub mod bridge;
+#[cfg(feature = "engine")]
+pub use bridge::LogBridge;
@@ -1,9 +1,25 @@
 use rill_protocol::flow::core::Flow;
-use rill_protocol::io::provider::StreamType;
-use rrpack_basis::frames::Frame;
+use rill_protocol::io::provider::{StreamType, Timestamp};
+use rrpack_basis::frames::{TimedEvent, TimedFrame};
 use rrpack_basis::manifest::description::{Layer, PackFlow};
 use serde::{Deserialize, Serialize};
 
+/// Known levels from the lowest to the highest.
+pub const LEVELS: &[&str] = &["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+
+/// Returns a known name of the level in upper case.
+pub fn level_name(level: &str) -> Option<&'static str> {
+    level_rank(level).map(|rank| LEVELS[rank])
+}
+
+/// Returns a rank of the level or `None` if the level is unknown.
+pub fn level_rank(level: &str) -> Option<usize> {
+    LEVELS
+        .iter()
+        .position(|known| known.eq_ignore_ascii_case(level))
+}
+
+/// A log record. Records are retained by timestamps of their events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogRecord {
     pub module: String,
@@ -12,19 +28,76 @@ pub struct LogRecord {
     pub content: String,
 }
 
+/// Records that don't match the filter are not added to the frame.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Records with unknown levels are always kept.
+    pub min_level: Option<String>,
+    /// A prefix of modules.
+    pub module: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let min_rank = self.min_level.as_deref().and_then(level_rank);
+        let level_matched = min_rank
+            .zip(level_rank(&record.level))
+            .map(|(min, rank)| rank >= min)
+            .unwrap_or(true);
+        let module_matched = self
+            .module
+            .as_ref()
+            .map(|prefix| record.module.starts_with(prefix.as_str()))
+            .unwrap_or(true);
+        level_matched && module_matched
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LiveTailSpec;
+pub struct LiveTailSpec {
+    /// Max amount of records to keep.
+    pub capacity: u32,
+    /// How long to keep records (in seconds). Records never expire if not set.
+    pub expire: Option<u32>,
+}
+
+impl Default for LiveTailSpec {
+    fn default() -> Self {
+        Self {
+            capacity: 50,
+            expire: None,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiveTailState {
     pub spec: LiveTailSpec,
-    pub frame: Frame<LogRecord>,
+    pub filter: LogFilter,
+    pub frame: TimedFrame<LogRecord>,
 }
 
 impl From<LiveTailSpec> for LiveTailState {
     fn from(spec: LiveTailSpec) -> Self {
-        let frame = Frame::new(50); // TODO: Get from spec
-        Self { spec, frame }
+        let depth_ms = spec.expire.map(|secs| secs as i64 * 1_000);
+        let frame = TimedFrame::with_limits(depth_ms, Some(spec.capacity));
+        Self {
+            spec,
+            filter: LogFilter::default(),
+            frame,
+        }
+    }
+}
+
+impl LiveTailState {
+    /// Checks the oldest record has to be removed at the `timestamp`.
+    pub fn has_expired(&self, timestamp: Timestamp) -> bool {
+        self.frame.has_expired(timestamp)
+    }
+
+    /// Records that match the filter.
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &TimedEvent<LogRecord>> {
+        self.frame.iter()
     }
 }
 
@@ -35,7 +108,7 @@ impl PackFlow for LiveTailState {
 }
 
 impl Flow for LiveTailState {
-    type Action = ();
+    type Action = LiveTailAction;
     type Event = LiveTailEvent;
 
     fn stream_type() -> StreamType {
@@ -45,13 +118,106 @@ impl Flow for LiveTailState {
     fn apply(&mut self, event: Self::Event) {
         match event {
             LiveTailEvent::Add(record) => {
-                self.frame.insert_pop(record);
+                if self.filter.matches(&record.event) {
+                    self.frame.insert_pop(record);
+                }
+            }
+            LiveTailEvent::SetFilter(filter) => {
+                // Records of the previous filter are not mixed with new ones
+                if self.filter != filter {
+                    self.frame.clear();
+                    self.filter = filter;
+                }
+            }
+            LiveTailEvent::Expire { timestamp } => {
+                self.frame.expire(timestamp);
             }
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LiveTailAction {
+    SetFilter(LogFilter),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LiveTailEvent {
-    Add(LogRecord),
+    Add(TimedEvent<LogRecord>),
+    SetFilter(LogFilter),
+    /// Removes expired records if no new records are added.
+    Expire {
+        timestamp: Timestamp,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(state: &mut LiveTailState, module: &str, level: &str) {
+        let record = LogRecord {
+            module: module.into(),
+            level: level.into(),
+            timestamp: String::new(),
+            content: String::new(),
+        };
+        let event = TimedEvent {
+            timestamp: Timestamp(0),
+            event: record,
+        };
+        state.apply(LiveTailEvent::Add(event));
+    }
+
+    #[test]
+    fn test_live_tail_filter() {
+        let spec = LiveTailSpec {
+            capacity: 2,
+            expire: None,
+        };
+        let mut state = LiveTailState::from(spec);
+        add(&mut state, "app::db", "DEBUG");
+        let filter = LogFilter {
+            min_level: Some("INFO".into()),
+            module: Some("app::".into()),
+        };
+        state.apply(LiveTailEvent::SetFilter(filter.clone()));
+        assert_eq!(state.records().count(), 0);
+        add(&mut state, "app::net", "warn");
+        add(&mut state, "app::db", "CUSTOM");
+        // Hidden records don't push matched records out
+        add(&mut state, "app::db", "TRACE");
+        add(&mut state, "lib", "ERROR");
+        assert_eq!(state.records().count(), 2);
+        // The same filter keeps records
+        state.apply(LiveTailEvent::SetFilter(filter));
+        assert_eq!(state.records().count(), 2);
+        state.apply(LiveTailEvent::SetFilter(LogFilter::default()));
+        assert_eq!(state.records().count(), 0);
+        add(&mut state, "lib", "TRACE");
+        assert_eq!(state.records().count(), 1);
+    }
+
+    #[test]
+    fn test_live_tail_expire() {
+        let spec = LiveTailSpec {
+            capacity: 10,
+            expire: Some(1),
+        };
+        let mut state = LiveTailState::from(spec);
+        add(&mut state, "app", "INFO");
+        assert!(!state.has_expired(Timestamp(999)));
+        assert!(state.has_expired(Timestamp(1_000)));
+        state.apply(LiveTailEvent::Expire {
+            timestamp: Timestamp(1_000),
+        });
+        assert_eq!(state.frame.len(), 0);
+    }
+
+    #[test]
+    fn test_level_name() {
+        assert_eq!(level_name("warn"), Some("WARN"));
+        assert_eq!(level_name("Error"), Some("ERROR"));
+        assert_eq!(level_name("CUSTOM"), None);
+    }
 }
@@ -1,17 +1,29 @@
 use super::state::*;
+use chrono::{DateTime, Local};
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::{time_to_ts, TimedEvent};
 use rrpack_basis::{AutoPath, BindedTracer};
+use std::time::{Duration, SystemTime};
+
+/// How often records are checked for the expiration.
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(TracerOpts, Clone, Default)]
 pub struct LiveTailOpts {
     // TODO: Add levels here (maybe)
+    pub capacity: Option<u32>,
+    pub expire: Option<u32>,
 }
 
 impl From<LiveTailOpts> for LiveTailSpec {
-    fn from(_opts: LiveTailOpts) -> Self {
-        Self {}
+    fn from(opts: LiveTailOpts) -> Self {
+        let default = LiveTailSpec::default();
+        Self {
+            capacity: opts.capacity.unwrap_or(default.capacity),
+            expire: opts.expire,
+        }
     }
 }
 
@@ -26,10 +38,29 @@ impl LiveTail {
         mode: FlowMode,
         spec: impl Into<LiveTailSpec>,
     ) -> Self {
-        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        let spec = spec.into();
+        let expirable = spec.expire.is_some();
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec);
+        // Filters are set from the dashboard without callbacks
+        tracer.reactor(|_: &LiveTailState, action: &LiveTailAction| match action {
+            LiveTailAction::SetFilter(filter) => Some(LiveTailEvent::SetFilter(filter.clone())),
+        });
+        if expirable {
+            // Records have to be removed even if nothing is logged anymore
+            tracer.ticker(EXPIRE_INTERVAL, |state: &LiveTailState| {
+                let timestamp = time_to_ts(None).ok()?;
+                if state.has_expired(timestamp) {
+                    Some(LiveTailEvent::Expire { timestamp })
+                } else {
+                    None
+                }
+            });
+        }
         Self { tracer }
     }
 
+    /// Adds a record with a formatted `timestamp`.
+    /// The record is retained by the time it was added.
     pub fn log(
         &self,
         module: impl Into<String>,
@@ -43,24 +74,57 @@ impl LiveTail {
             timestamp: timestamp.into(),
             content: content.into(),
         };
-        let msg = LiveTailEvent::Add(record);
-        self.tracer.send(msg, None);
+        self.add(None, record);
     }
 
-    pub fn log_now(
+    /// Adds a record that happened at the `time`.
+    pub fn log_at(
         &self,
         module: impl Into<String>,
         level: impl Into<String>,
+        time: SystemTime,
         content: impl Into<String>,
     ) {
-        let timestamp = chrono::Local::now().format("%F%T%.3f").to_string();
+        let timestamp = DateTime::<Local>::from(time)
+            .format("%F %T%.3f")
+            .to_string();
         let record = LogRecord {
             module: module.into(),
             level: level.into(),
             timestamp,
             content: content.into(),
         };
-        let msg = LiveTailEvent::Add(record);
+        self.add(Some(time), record);
+    }
+
+    pub fn log_now(
+        &self,
+        module: impl Into<String>,
+        level: impl Into<String>,
+        content: impl Into<String>,
+    ) {
+        self.log_at(module, level, SystemTime::now(), content);
+    }
+
+    /// Records that don't match the `filter` are skipped.
+    pub fn set_filter(&self, filter: LogFilter) {
+        let msg = LiveTailEvent::SetFilter(filter);
         self.tracer.send(msg, None);
     }
+
+    fn add(&self, time: Option<SystemTime>, record: LogRecord) {
+        match time_to_ts(time) {
+            Ok(timestamp) => {
+                let event = TimedEvent {
+                    timestamp,
+                    event: record,
+                };
+                let msg = LiveTailEvent::Add(event);
+                self.tracer.send(msg, None);
+            }
+            Err(err) => {
+                log::error!("Can't get a timestamp of a log record: {}", err);
+            }
+        }
+    }
 }
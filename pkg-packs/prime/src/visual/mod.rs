@@ -12,6 +12,10 @@ pub mod gauge;
 #[cfg(feature = "engine")]
 pub use gauge::{Gauge, GaugeOpts};
 
+pub mod heatmap;
+#[cfg(feature = "engine")]
+pub use heatmap::{Heatmap, HeatmapOpts};
+
 pub mod histogram;
 #[cfg(feature = "engine")]
 pub use histogram::{Histogram, HistogramOpts};
@@ -22,12 +26,24 @@ pub use live_text::{LiveText, LiveTextOpts};
 
 pub mod live_tail;
 #[cfg(feature = "engine")]
-pub use live_tail::{LiveTail, LiveTailOpts};
+pub use live_tail::{LiveTail, LiveTailOpts, LogBridge};
+
+pub mod multi_pulse;
+#[cfg(feature = "engine")]
+pub use multi_pulse::{MultiPulse, MultiPulseOpts};
 
 pub mod pulse;
 #[cfg(feature = "engine")]
 pub use pulse::{Pulse, PulseOpts};
 
+pub mod sketch;
+#[cfg(feature = "engine")]
+pub use sketch::{Sketch, SketchOpts};
+
+pub mod status;
+#[cfg(feature = "engine")]
+pub use status::{Status, StatusOpts};
+
 pub mod table;
 #[cfg(feature = "engine")]
 pub use table::{Table, TableOpts};
@@ -0,0 +1,9 @@
+//! Live-chart with multiple named series.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
@@ -0,0 +1,200 @@
+use crate::range::{Label, Range};
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::StreamType;
+use rrpack_basis::frames::{new_tf, TimedEvent, TimedFrame};
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeriesSpec {
+    pub name: String,
+    /// Caption of the series. The `name` used if not set.
+    pub caption: Option<String>,
+    /// CSS color of the line (`#RRGGBB`). Picked automatically if not set.
+    pub color: Option<String>,
+}
+
+impl SeriesSpec {
+    pub fn caption(&self) -> &str {
+        self.caption.as_ref().unwrap_or(&self.name)
+    }
+}
+
+impl From<&str> for SeriesSpec {
+    fn from(name: &str) -> Self {
+        Self::from(name.to_string())
+    }
+}
+
+impl From<String> for SeriesSpec {
+    fn from(name: String) -> Self {
+        Self {
+            name,
+            caption: None,
+            color: None,
+        }
+    }
+}
+
+impl From<(&str, &str)> for SeriesSpec {
+    fn from((name, color): (&str, &str)) -> Self {
+        Self {
+            name: name.into(),
+            caption: None,
+            color: Some(color.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiPulseSpec {
+    pub retain: u32,
+    pub range: Range,
+    pub label: Label,
+    pub series: Vec<SeriesSpec>,
+}
+
+impl Default for MultiPulseSpec {
+    fn default() -> Self {
+        Self {
+            retain: 30,
+            range: Range::default(),
+            label: Label::default(),
+            series: Vec::new(),
+        }
+    }
+}
+
+impl MultiPulseSpec {
+    pub fn series(&self, name: &str) -> Option<&SeriesSpec> {
+        self.series.iter().find(|series| series.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiPulseState {
+    pub spec: MultiPulseSpec,
+    /// Series not declared in the spec are added on the first push.
+    #[serde(with = "vectorize")]
+    pub frames: BTreeMap<String, TimedFrame<f64>>,
+}
+
+impl From<MultiPulseSpec> for MultiPulseState {
+    fn from(spec: MultiPulseSpec) -> Self {
+        let frames = spec
+            .series
+            .iter()
+            .map(|series| (series.name.clone(), new_tf(spec.retain as i64 + 1)))
+            .collect();
+        Self { spec, frames }
+    }
+}
+
+impl MultiPulseState {
+    /// Series in the order of the spec. Undeclared series follow sorted by names.
+    pub fn ordered_series(&self) -> Vec<(&str, &TimedFrame<f64>)> {
+        let declared = self.spec.series.iter().filter_map(|series| {
+            let name = series.name.as_str();
+            self.frames.get(name).map(|frame| (name, frame))
+        });
+        let undeclared = self
+            .frames
+            .iter()
+            .filter(|(name, _)| self.spec.series(name).is_none())
+            .map(|(name, frame)| (name.as_str(), frame));
+        declared.chain(undeclared).collect()
+    }
+}
+
+impl PackFlow for MultiPulseState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for MultiPulseState {
+    type Action = ();
+    type Event = MultiPulseEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            MultiPulseEvent::Push { series, value } => {
+                let retain = self.spec.retain as i64 + 1;
+                self.frames
+                    .entry(series)
+                    .or_insert_with(|| new_tf(retain))
+                    .insert_pop(value);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MultiPulseEvent {
+    Push {
+        series: String,
+        value: TimedEvent<f64>,
+    },
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn push(state: &mut MultiPulseState, series: &str, ts: i64, value: f64) {
+        let value = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: value,
+        };
+        let series = series.into();
+        state.apply(MultiPulseEvent::Push { series, value });
+    }
+
+    fn values(state: &MultiPulseState, series: &str) -> Vec<f64> {
+        state.frames[series].iter().map(|item| item.event).collect()
+    }
+
+    #[test]
+    fn test_series_order() {
+        let spec = MultiPulseSpec {
+            series: vec!["b".into(), "a".into()],
+            ..MultiPulseSpec::default()
+        };
+        let mut state = MultiPulseState::from(spec);
+        push(&mut state, "d", 1, 1.0);
+        push(&mut state, "c", 1, 1.0);
+        let names: Vec<_> = state
+            .ordered_series()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["b", "a", "c", "d"]);
+    }
+
+    #[test]
+    fn test_series_frames() {
+        let spec = MultiPulseSpec {
+            retain: 5,
+            series: vec!["a".into()],
+            ..MultiPulseSpec::default()
+        };
+        let mut state = MultiPulseState::from(spec);
+        assert!(state.frames["a"].iter().next().is_none());
+        push(&mut state, "a", 1_000, 1.0);
+        push(&mut state, "b", 2_000, 2.0);
+        push(&mut state, "a", 3_000, 3.0);
+        assert_eq!(values(&state, "a"), vec![1.0, 3.0]);
+        assert_eq!(values(&state, "b"), vec![2.0]);
+        // Points older than `retain` seconds are removed from the series only
+        push(&mut state, "a", 8_000, 8.0);
+        assert_eq!(values(&state, "a"), vec![3.0, 8.0]);
+        assert_eq!(values(&state, "b"), vec![2.0]);
+    }
+}
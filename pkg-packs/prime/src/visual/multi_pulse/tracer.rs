@@ -0,0 +1,63 @@
+use super::state::*;
+use crate::range::{Bound, Label, Range};
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct MultiPulseOpts {
+    pub retain: Option<u32>,
+
+    pub suffix: Option<String>,
+    pub divisor: Option<f64>,
+
+    pub min: Option<f64>,
+    pub lower: Option<bool>,
+    pub max: Option<f64>,
+    pub higher: Option<bool>,
+
+    pub series: Vec<SeriesSpec>,
+}
+
+impl From<MultiPulseOpts> for MultiPulseSpec {
+    fn from(opts: MultiPulseOpts) -> Self {
+        Self {
+            retain: opts.retain.unwrap_or(30),
+            label: Label::from_options(opts.suffix, opts.divisor),
+            range: Range {
+                min: Bound::from_options(opts.min, opts.lower),
+                max: Bound::from_options(opts.max, opts.higher),
+            },
+            series: opts.series,
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct MultiPulse {
+    tracer: BindedTracer<MultiPulseState>,
+}
+
+impl MultiPulse {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<MultiPulseSpec>,
+    ) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    /// Pushes a value to the series. Unknown series will be added.
+    pub fn push(&self, series: impl Into<String>, value: impl Into<f64>) {
+        if let Some(value) = timed(value.into()) {
+            let msg = MultiPulseEvent::Push {
+                series: series.into(),
+                value,
+            };
+            self.tracer.send(msg, None);
+        }
+    }
+}
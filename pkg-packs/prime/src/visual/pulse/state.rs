@@ -1,13 +1,23 @@
 use crate::range::{Label, Range};
 use rill_protocol::flow::core::Flow;
 use rill_protocol::io::provider::StreamType;
-use rrpack_basis::frames::{new_tf, TimedEvent, TimedFrame};
+use rill_protocol::io::provider::Timestamp;
+use rrpack_basis::frames::{new_tf, DownsampledFrame, TimedEvent, TimedFrame};
 use rrpack_basis::manifest::description::{Layer, PackFlow};
 use serde::{Deserialize, Serialize};
 
+/// Amount of aggregated points to keep for the whole `retain` period.
+const HISTORY_POINTS: i64 = 300;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PulseSpec {
+    /// How long to keep points (in seconds).
     pub retain: u32,
+    /// Max amount of points to keep.
+    pub limit: Option<u32>,
+    /// How long to keep points with the full resolution (in seconds).
+    /// Older points are aggregated if it's set.
+    pub detailed: Option<u32>,
     pub range: Range,
     pub label: Label,
 }
@@ -16,6 +26,8 @@ impl Default for PulseSpec {
     fn default() -> Self {
         Self {
             retain: 30,
+            limit: None,
+            detailed: None,
             range: Range::default(),
             label: Label::default(),
         }
@@ -25,13 +37,46 @@ impl Default for PulseSpec {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PulseState {
     pub spec: PulseSpec,
+    /// Recent points with the full resolution.
     pub frame: TimedFrame<f64>,
+    /// Aggregated points that are older than the `frame`.
+    pub history: Option<DownsampledFrame>,
 }
 
 impl From<PulseSpec> for PulseState {
     fn from(spec: PulseSpec) -> Self {
-        let frame = new_tf(spec.retain as i64 + 1);
-        Self { spec, frame }
+        let retain = spec.retain as i64 + 1;
+        let detailed = spec.detailed.map(|secs| (secs as i64).min(retain));
+        let mut frame = new_tf(detailed.unwrap_or(retain));
+        frame.set_limit(spec.limit);
+        let history = detailed.map(|secs| {
+            let depth_ms = (retain - secs) * 1_000;
+            // Rounded up to never exceed the amount of points
+            let interval_ms = (retain * 1_000 + HISTORY_POINTS - 1) / HISTORY_POINTS;
+            DownsampledFrame::new(depth_ms, interval_ms)
+        });
+        Self {
+            spec,
+            frame,
+            history,
+        }
+    }
+}
+
+impl PulseState {
+    /// Returns averages of aggregated points followed by recent points.
+    pub fn points(&self) -> impl Iterator<Item = (Timestamp, f64)> + '_ {
+        let history = self
+            .history
+            .iter()
+            .flat_map(|history| history.iter())
+            .map(|bucket| (bucket.timestamp, bucket.event.avg()));
+        let recent = self.frame.iter().map(|item| (item.timestamp, item.event));
+        history.chain(recent)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frame.is_empty() && self.history.iter().all(|h| h.is_empty())
     }
 }
 
@@ -52,7 +97,12 @@ impl Flow for PulseState {
     fn apply(&mut self, event: Self::Event) {
         match event {
             PulseEvent::Push { value } => {
-                self.frame.insert_pop(value);
+                let expired = self.frame.insert_pop(value);
+                if let Some(history) = self.history.as_mut() {
+                    for item in expired {
+                        history.insert_pop(item);
+                    }
+                }
             }
         }
     }
@@ -62,3 +112,40 @@ impl Flow for PulseState {
 pub enum PulseEvent {
     Push { value: TimedEvent<f64> },
 }
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    fn push(state: &mut PulseState, ts: i64, value: f64) {
+        let value = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: value,
+        };
+        state.apply(PulseEvent::Push { value });
+    }
+
+    #[test]
+    fn test_history_points() {
+        let spec = PulseSpec {
+            retain: 30,
+            detailed: Some(0),
+            ..PulseSpec::default()
+        };
+        let mut state = PulseState::from(spec);
+        // Spikes every second
+        for ts in (0..120_000).step_by(10) {
+            let value = if ts % 1_000 == 0 { 100.0 } else { 1.0 };
+            push(&mut state, ts, value);
+        }
+        let history = state.history.as_ref().unwrap();
+        assert!(history.len() <= HISTORY_POINTS as usize);
+        // The whole period is covered
+        assert!(history.len() > HISTORY_POINTS as usize * 9 / 10);
+        let min = history.iter().map(|b| b.event.min).fold(f64::MAX, f64::min);
+        let max = history.iter().map(|b| b.event.max).fold(f64::MIN, f64::max);
+        assert_eq!((min, max), (1.0, 100.0));
+        assert_eq!(state.points().count(), history.len() + state.frame.len());
+    }
+}
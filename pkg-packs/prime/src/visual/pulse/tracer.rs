@@ -1,5 +1,6 @@
 use super::state::*;
 use crate::range::{Bound, Label, Range};
+use crate::transparent::alert::{Alert, AlertRule};
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
@@ -9,6 +10,8 @@ use rrpack_basis::{AutoPath, BindedTracer};
 #[derive(TracerOpts, Clone, Default)]
 pub struct PulseOpts {
     pub retain: Option<u32>,
+    pub limit: Option<u32>,
+    pub detailed: Option<u32>,
 
     pub suffix: Option<String>,
     pub divisor: Option<f64>,
@@ -23,6 +26,8 @@ impl From<PulseOpts> for PulseSpec {
     fn from(opts: PulseOpts) -> Self {
         Self {
             retain: opts.retain.unwrap_or(30),
+            limit: opts.limit,
+            detailed: opts.detailed,
             label: Label::from_options(opts.suffix, opts.divisor),
             range: Range {
                 min: Bound::from_options(opts.min, opts.lower),
@@ -43,6 +48,13 @@ impl Pulse {
         Self { tracer }
     }
 
+    /// Attaches a rule that sends notifications to the `alert`.
+    pub fn add_alert(&self, alert: &Alert, rule: AlertRule) {
+        rule.attach(&self.tracer, alert, |state: &PulseState| {
+            state.frame.back().map(|item| item.event)
+        });
+    }
+
     pub fn push(&self, value: impl Into<f64>) {
         if let Some(value) = timed(value.into()) {
             let msg = PulseEvent::Push { value };
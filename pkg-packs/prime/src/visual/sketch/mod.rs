@@ -0,0 +1,9 @@
+//! Mergeable sketch (DDSketch) with accurate quantiles.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
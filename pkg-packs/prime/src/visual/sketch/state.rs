@@ -0,0 +1,428 @@
+use crate::range::Label;
+use ordered_float::OrderedFloat;
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::{StreamType, Timestamp};
+use rrpack_basis::frames::{new_tf, TimedEvent, TimedFrame};
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Values with a lower magnitude are tracked as zeros.
+const MIN_VALUE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SketchSpec {
+    /// Relative accuracy of quantiles (in range `(0;1)`).
+    pub accuracy: f64,
+    /// Max amount of bins for positive and negative values each.
+    pub max_bins: u32,
+    /// Quantiles (in range `[0;1]`) to draw.
+    pub quantiles: Vec<f64>,
+    /// Seconds between samples of quantiles.
+    pub interval: u32,
+    /// Amount of the latest intervals which values are sampled.
+    /// `1` means the sketch is reset every interval.
+    pub window: u32,
+    pub retain: u32,
+    pub label: Label,
+}
+
+impl Default for SketchSpec {
+    fn default() -> Self {
+        Self {
+            accuracy: 0.01,
+            max_bins: 2_048,
+            quantiles: vec![0.5, 0.9, 0.99],
+            interval: 1,
+            window: 10,
+            retain: 30,
+            label: Label::default(),
+        }
+    }
+}
+
+impl SketchSpec {
+    /// Creates an empty sketch compatible with the flow.
+    pub fn sketch(&self) -> DDSketch {
+        DDSketch::new(self.accuracy, self.max_bins)
+    }
+}
+
+/// Mergeable quantiles sketch with relative accuracy guarantees.
+///
+/// Every bin with the `key` covers values in range `(gamma^(key-1); gamma^key]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DDSketch {
+    gamma: OrderedFloat<f64>,
+    max_bins: u32,
+    #[serde(with = "vectorize")]
+    positive: BTreeMap<i32, u64>,
+    #[serde(with = "vectorize")]
+    negative: BTreeMap<i32, u64>,
+    zero: u64,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl DDSketch {
+    pub fn new(accuracy: f64, max_bins: u32) -> Self {
+        let accuracy = if accuracy > 0.0 && accuracy < 1.0 {
+            accuracy
+        } else {
+            SketchSpec::default().accuracy
+        };
+        let gamma = (1.0 + accuracy) / (1.0 - accuracy);
+        Self {
+            gamma: OrderedFloat(gamma),
+            max_bins: max_bins.max(1),
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+            zero: 0,
+            count: 0,
+            sum: 0.0,
+            min: f64::MAX,
+            max: f64::MIN,
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.non_empty(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.non_empty(self.max)
+    }
+
+    fn non_empty(&self, value: f64) -> Option<f64> {
+        if self.count > 0 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn key(&self, value: f64) -> i32 {
+        (value.ln() / self.gamma.ln()).ceil() as i32
+    }
+
+    fn value(&self, key: i32) -> f64 {
+        2.0 * self.gamma.powi(key) / (*self.gamma + 1.0)
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if value > MIN_VALUE {
+            let key = self.key(value);
+            *self.positive.entry(key).or_default() += 1;
+        } else if value < -MIN_VALUE {
+            let key = self.key(-value);
+            *self.negative.entry(key).or_default() += 1;
+        } else {
+            self.zero += 1;
+        }
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.collapse();
+    }
+
+    /// Both sketches have to be created with the same accuracy to be merged.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.gamma == other.gamma
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (key, count) in &other.positive {
+            *self.positive.entry(*key).or_default() += count;
+        }
+        for (key, count) in &other.negative {
+            *self.negative.entry(*key).or_default() += count;
+        }
+        self.zero += other.zero;
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.collapse();
+    }
+
+    /// Joins bins of the lowest magnitudes to keep the memory bounded.
+    fn collapse(&mut self) {
+        let max_bins = self.max_bins as usize;
+        for bins in [&mut self.positive, &mut self.negative] {
+            while bins.len() > max_bins {
+                let lowest = bins.keys().next().copied();
+                if let Some(count) = lowest.and_then(|key| bins.remove(&key)) {
+                    if let Some(next) = bins.values_mut().next() {
+                        *next += count;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = q.clamp(0.0, 1.0) * (self.count - 1) as f64;
+        let mut passed = 0;
+        for (key, count) in self.negative.iter().rev() {
+            passed += count;
+            if passed as f64 > rank {
+                return Some(self.clamp(-self.value(*key)));
+            }
+        }
+        passed += self.zero;
+        if passed as f64 > rank {
+            return Some(self.clamp(0.0));
+        }
+        for (key, count) in &self.positive {
+            passed += count;
+            if passed as f64 > rank {
+                return Some(self.clamp(self.value(*key)));
+            }
+        }
+        Some(self.max)
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SketchState {
+    pub spec: SketchSpec,
+    /// Values of the current interval.
+    pub sketch: DDSketch,
+    /// Values of previous intervals of the window.
+    pub closed: VecDeque<DDSketch>,
+    /// Values of quantiles from the spec over time.
+    /// Indexes match the quantiles of the spec, `None` if there is no data yet.
+    pub frame: TimedFrame<Vec<Option<f64>>>,
+}
+
+impl From<SketchSpec> for SketchState {
+    fn from(spec: SketchSpec) -> Self {
+        let sketch = spec.sketch();
+        let frame = new_tf(spec.retain as i64);
+        Self {
+            spec,
+            sketch,
+            closed: VecDeque::new(),
+            frame,
+        }
+    }
+}
+
+impl SketchState {
+    /// Merges all values of the window.
+    pub fn window(&self) -> DDSketch {
+        let mut window = self.spec.sketch();
+        for sketch in &self.closed {
+            window.merge(sketch);
+        }
+        window.merge(&self.sketch);
+        window
+    }
+
+    fn sample(&mut self, timestamp: Timestamp) {
+        let window = self.window();
+        let values = self
+            .spec
+            .quantiles
+            .iter()
+            .map(|q| window.quantile(*q))
+            .collect();
+        let point = TimedEvent {
+            timestamp,
+            event: values,
+        };
+        self.frame.insert_pop(point);
+        // Starts the next interval
+        let depth = self.spec.window.saturating_sub(1) as usize;
+        let closed = std::mem::replace(&mut self.sketch, self.spec.sketch());
+        self.closed.push_back(closed);
+        while self.closed.len() > depth {
+            self.closed.pop_front();
+        }
+    }
+}
+
+impl PackFlow for SketchState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for SketchState {
+    type Action = ();
+    type Event = SketchEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            SketchEvent::Add(value) => {
+                self.sketch.add(value);
+            }
+            SketchEvent::Merge { delta } => {
+                if self.sketch.is_compatible(&delta) {
+                    self.sketch.merge(&delta);
+                } else {
+                    log::error!("Incompatible sketch can't be merged.");
+                }
+            }
+            SketchEvent::Sample { timestamp } => {
+                self.sample(timestamp);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SketchEvent {
+    /// A single sample.
+    Add(f64),
+    /// Samples aggregated locally.
+    Merge { delta: DDSketch },
+    /// Draws quantiles of the window and starts the next interval.
+    Sample { timestamp: Timestamp },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn assert_accurate(sketch: &DDSketch, q: f64, expected: f64) {
+        let value = sketch.quantile(q).unwrap();
+        let error = (value - expected).abs() / expected.abs();
+        assert!(error <= 0.01, "q={}: {} != {}", q, value, expected);
+    }
+
+    #[test]
+    fn test_sketch_quantiles() {
+        let mut sketch = DDSketch::new(0.01, 2_048);
+        assert_eq!(sketch.quantile(0.5), None);
+        for value in 1..=1_000 {
+            sketch.add(value as f64);
+        }
+        assert_eq!(sketch.count(), 1_000);
+        assert_accurate(&sketch, 0.0, 1.0);
+        assert_accurate(&sketch, 0.5, 500.0);
+        assert_accurate(&sketch, 0.99, 990.0);
+        assert_accurate(&sketch, 1.0, 1_000.0);
+    }
+
+    #[test]
+    fn test_sketch_merge() {
+        let mut negative = DDSketch::new(0.01, 2_048);
+        let mut positive = DDSketch::new(0.01, 2_048);
+        for value in 1..=100 {
+            negative.add(-value as f64);
+            positive.add(value as f64);
+        }
+        negative.add(0.0);
+        assert!(negative.is_compatible(&positive));
+        negative.merge(&positive);
+        assert_eq!(negative.count(), 201);
+        assert_accurate(&negative, 0.0, -100.0);
+        assert_eq!(negative.quantile(0.5), Some(0.0));
+        assert_accurate(&negative, 0.75, 50.0);
+        assert!(!negative.is_compatible(&DDSketch::new(0.05, 2_048)));
+    }
+
+    #[test]
+    fn test_sketch_bounded() {
+        let mut sketch = DDSketch::new(0.01, 64);
+        for value in 1..=10_000 {
+            sketch.add(value as f64);
+        }
+        assert!(sketch.positive.len() <= 64);
+        assert_eq!(sketch.count(), 10_000);
+        assert_accurate(&sketch, 0.99, 9_900.0);
+    }
+
+    #[test]
+    fn test_sketch_state_sampled() {
+        let spec = SketchSpec {
+            quantiles: vec![0.0, 1.0],
+            window: 2,
+            ..SketchSpec::default()
+        };
+        let mut state = SketchState::from(spec);
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(1_000),
+        });
+        state.apply(SketchEvent::Add(10.0));
+        state.apply(SketchEvent::Merge {
+            delta: state.spec.sketch(),
+        });
+        // Values don't add points
+        assert_eq!(state.frame.len(), 1);
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(2_000),
+        });
+        state.apply(SketchEvent::Add(20.0));
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(3_000),
+        });
+        // The value of the first interval is out of the window
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(4_000),
+        });
+        let points: Vec<_> = state.frame.iter().map(|p| p.event.clone()).collect();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[0], vec![None, None]);
+        assert_accurate_opt(points[1][0], 10.0);
+        assert_accurate_opt(points[2][0], 10.0);
+        assert_accurate_opt(points[2][1], 20.0);
+        assert_accurate_opt(points[3][0], 20.0);
+        assert_eq!(state.closed.len(), 1);
+        assert_eq!(state.window().count(), 0);
+    }
+
+    #[test]
+    fn test_sketch_state_reset() {
+        let spec = SketchSpec {
+            window: 1,
+            ..SketchSpec::default()
+        };
+        let mut state = SketchState::from(spec);
+        state.apply(SketchEvent::Add(10.0));
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(1_000),
+        });
+        assert!(state.closed.is_empty());
+        assert_eq!(state.window().count(), 0);
+        state.apply(SketchEvent::Sample {
+            timestamp: Timestamp(2_000),
+        });
+        let points: Vec<_> = state.frame.iter().map(|p| p.event.clone()).collect();
+        assert!(points[0].iter().all(Option::is_some));
+        assert_eq!(points[1], vec![None, None, None]);
+    }
+
+    fn assert_accurate_opt(value: Option<f64>, expected: f64) {
+        let value = value.unwrap();
+        let error = (value - expected).abs() / expected.abs();
+        assert!(error <= 0.01, "{} != {}", value, expected);
+    }
+}
@@ -0,0 +1,91 @@
+use super::state::*;
+use crate::range::Label;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_basis::{AutoPath, BindedTracer};
+use std::time::Duration;
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct SketchOpts {
+    pub accuracy: Option<f64>,
+    pub max_bins: Option<u32>,
+    pub quantiles: Vec<f64>,
+    pub interval: Option<u32>,
+    pub window: Option<u32>,
+    pub retain: Option<u32>,
+
+    pub suffix: Option<String>,
+    pub divisor: Option<f64>,
+}
+
+impl From<SketchOpts> for SketchSpec {
+    fn from(opts: SketchOpts) -> Self {
+        let default = SketchSpec::default();
+        let quantiles = if opts.quantiles.is_empty() {
+            default.quantiles
+        } else {
+            opts.quantiles
+        };
+        Self {
+            accuracy: opts.accuracy.unwrap_or(default.accuracy),
+            max_bins: opts.max_bins.unwrap_or(default.max_bins),
+            quantiles,
+            interval: opts.interval.unwrap_or(default.interval).max(1),
+            window: opts.window.unwrap_or(default.window).max(1),
+            retain: opts.retain.unwrap_or(default.retain),
+            label: Label::from_options(opts.suffix, opts.divisor),
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Sketch {
+    #[deref]
+    #[deref_mut]
+    tracer: BindedTracer<SketchState>,
+    accuracy: f64,
+    max_bins: u32,
+}
+
+impl Sketch {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<SketchSpec>,
+    ) -> Self {
+        let spec = spec.into();
+        let accuracy = spec.accuracy;
+        let max_bins = spec.max_bins;
+        let interval = Duration::from_secs(spec.interval.max(1) as u64);
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec);
+        // Values are only collected by the sketch, quantiles are drawn periodically
+        tracer.ticker(interval, |_: &SketchState| {
+            let timestamp = timed(())?.timestamp;
+            Some(SketchEvent::Sample { timestamp })
+        });
+        Self {
+            tracer,
+            accuracy,
+            max_bins,
+        }
+    }
+
+    /// Creates an empty sketch that can be merged into the flow.
+    ///
+    /// Use it to aggregate values locally and send them with the `merge` call.
+    pub fn sketch(&self) -> DDSketch {
+        DDSketch::new(self.accuracy, self.max_bins)
+    }
+
+    pub fn add(&self, value: impl Into<f64>) {
+        let msg = SketchEvent::Add(value.into());
+        self.tracer.send(msg, None);
+    }
+
+    pub fn merge(&self, sketch: DDSketch) {
+        let msg = SketchEvent::Merge { delta: sketch };
+        self.tracer.send(msg, None);
+    }
+}
@@ -0,0 +1,9 @@
+//! Traffic-light indicator of a health state.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
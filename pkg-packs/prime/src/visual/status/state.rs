@@ -0,0 +1,179 @@
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::{StreamType, Timestamp};
+use rrpack_basis::frames::{Frame, TimedEvent};
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StatusSpec {
+    /// Amount of level changes to keep. History is not kept if not set.
+    pub history: Option<u32>,
+}
+
+/// Levels are ordered from the best to the worst.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Ok,
+    Degraded,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Indicator {
+    pub level: Level,
+    pub message: String,
+    /// When the level was changed.
+    pub changed: Timestamp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// The status of the whole group if not set.
+    pub child: Option<String>,
+    pub level: Level,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusState {
+    pub spec: StatusSpec,
+    /// The own status of the group.
+    pub indicator: Option<Indicator>,
+    #[serde(with = "vectorize")]
+    pub children: BTreeMap<String, Indicator>,
+    /// Reports that changed levels.
+    pub history: Option<Frame<TimedEvent<Report>>>,
+}
+
+impl From<StatusSpec> for StatusState {
+    fn from(spec: StatusSpec) -> Self {
+        let history = spec.history.map(Frame::new);
+        Self {
+            spec,
+            indicator: None,
+            children: BTreeMap::new(),
+            history,
+        }
+    }
+}
+
+impl StatusState {
+    /// The worst indicator of the group including children.
+    pub fn worst(&self) -> Option<&Indicator> {
+        self.indicator
+            .iter()
+            .chain(self.children.values())
+            // The earliest change of the worst level is the time in the state
+            .min_by_key(|indicator| (std::cmp::Reverse(indicator.level), indicator.changed))
+    }
+
+    pub fn level(&self) -> Option<Level> {
+        self.worst().map(|indicator| indicator.level)
+    }
+}
+
+impl PackFlow for StatusState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for StatusState {
+    type Action = ();
+    type Event = StatusEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            StatusEvent::Report(report) => {
+                let TimedEvent { timestamp, event } = report;
+                let indicator = match event.child.as_ref() {
+                    Some(child) => self.children.get_mut(child),
+                    None => self.indicator.as_mut(),
+                };
+                let changed = match indicator {
+                    Some(indicator) => {
+                        let changed = indicator.level != event.level;
+                        if changed {
+                            indicator.level = event.level;
+                            indicator.changed = timestamp;
+                        }
+                        indicator.message = event.message.clone();
+                        changed
+                    }
+                    None => {
+                        let indicator = Indicator {
+                            level: event.level,
+                            message: event.message.clone(),
+                            changed: timestamp,
+                        };
+                        match event.child.clone() {
+                            Some(child) => {
+                                self.children.insert(child, indicator);
+                            }
+                            None => {
+                                self.indicator = Some(indicator);
+                            }
+                        }
+                        true
+                    }
+                };
+                if changed {
+                    if let Some(history) = self.history.as_mut() {
+                        history.insert_pop(TimedEvent { timestamp, event });
+                    }
+                }
+            }
+            StatusEvent::Remove { child } => {
+                self.children.remove(&child);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StatusEvent {
+    Report(TimedEvent<Report>),
+    Remove { child: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(state: &mut StatusState, ts: i64, child: Option<&str>, level: Level) {
+        let report = Report {
+            child: child.map(String::from),
+            level,
+            message: String::new(),
+        };
+        let event = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: report,
+        };
+        state.apply(StatusEvent::Report(event));
+    }
+
+    #[test]
+    fn test_worst_status() {
+        let mut state = StatusState::from(StatusSpec { history: Some(10) });
+        assert_eq!(state.level(), None);
+        report(&mut state, 1, None, Level::Ok);
+        report(&mut state, 2, Some("db"), Level::Degraded);
+        report(&mut state, 3, Some("api"), Level::Degraded);
+        report(&mut state, 4, Some("db"), Level::Degraded);
+        assert_eq!(state.level(), Some(Level::Degraded));
+        assert_eq!(state.worst().unwrap().changed, Timestamp(2));
+        report(&mut state, 5, Some("api"), Level::Down);
+        assert_eq!(state.level(), Some(Level::Down));
+        state.apply(StatusEvent::Remove {
+            child: "api".into(),
+        });
+        assert_eq!(state.level(), Some(Level::Degraded));
+        assert_eq!(state.history.unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,64 @@
+use super::state::*;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct StatusOpts {
+    pub history: Option<u32>,
+}
+
+impl From<StatusOpts> for StatusSpec {
+    fn from(opts: StatusOpts) -> Self {
+        Self {
+            history: opts.history,
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Status {
+    tracer: BindedTracer<StatusState>,
+}
+
+impl Status {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<StatusSpec>,
+    ) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    /// Sets the own status of the group.
+    pub fn set(&self, level: Level, message: impl ToString) {
+        self.report(None, level, message);
+    }
+
+    /// Sets the status of a child. The group shows the worst status of all children.
+    pub fn set_child(&self, child: impl ToString, level: Level, message: impl ToString) {
+        self.report(Some(child.to_string()), level, message);
+    }
+
+    pub fn remove_child(&self, child: impl ToString) {
+        let msg = StatusEvent::Remove {
+            child: child.to_string(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    fn report(&self, child: Option<String>, level: Level, message: impl ToString) {
+        let report = Report {
+            child,
+            level,
+            message: message.to_string(),
+        };
+        if let Some(report) = timed(report) {
+            let msg = StatusEvent::Report(report);
+            self.tracer.send(msg, None);
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::range::Label;
 use derive_more::{From, Into};
 use rill_protocol::flow::core::Flow;
-use rill_protocol::io::provider::StreamType;
+use rill_protocol::io::provider::{StreamType, Timestamp};
+use rrpack_basis::frames::TimedEvent;
 use rrpack_basis::manifest::description::{Layer, PackFlow};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -11,6 +13,16 @@ use std::fmt;
 pub struct TableSpec {
     #[serde(with = "vectorize")]
     pub columns: BTreeMap<Col, ColRecord>,
+    /// The default order of rows.
+    pub sort: Option<Sort>,
+    /// Rows that were not updated for that amount of seconds are removed.
+    pub ttl: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Sort {
+    pub col: Col,
+    pub descending: bool,
 }
 
 /// Id of a column in a table.
@@ -78,48 +90,305 @@ impl PackFlow for TableState {
 
 impl Flow for TableState {
     type Action = ();
-    type Event = TableEvent;
+    type Event = TimedEvent<TableEvent>;
 
     fn stream_type() -> StreamType {
         StreamType::from(module_path!())
     }
 
     fn apply(&mut self, event: Self::Event) {
-        match event {
+        let updated = event.timestamp;
+        match event.event {
             TableEvent::AddRow { row } => {
-                let record = RowRecord {
-                    cols: BTreeMap::new(),
-                };
-                self.rows.insert(row, record);
+                self.rows.insert(row, RowRecord::new(updated));
             }
             TableEvent::DelRow { row } => {
                 self.rows.remove(&row);
             }
             TableEvent::SetCell { row, col, value } => {
-                if let Some(record) = self.rows.get_mut(&row) {
-                    if self.spec.columns.contains_key(&col) {
-                        record.cols.insert(col, value);
+                if let Some(cell) = self.cell_mut(row, col, updated) {
+                    cell.value = value;
+                }
+            }
+            TableEvent::SetStyle { row, col, style } => {
+                if let Some(cell) = self.cell_mut(row, col, updated) {
+                    cell.style = style;
+                }
+            }
+            TableEvent::SetRow { row, cells } => {
+                let record = self.new_row(cells, updated);
+                self.rows.insert(row, record);
+            }
+            TableEvent::UpsertRow { row, cells } => {
+                let columns = &self.spec.columns;
+                let record = self
+                    .rows
+                    .entry(row)
+                    .or_insert_with(|| RowRecord::new(updated));
+                record.updated = updated;
+                for (col, value) in cells {
+                    if columns.contains_key(&col) {
+                        record.cols.entry(col).or_default().value = value;
                     }
                 }
             }
+            TableEvent::SetRows { rows } => {
+                self.rows = rows
+                    .into_iter()
+                    .map(|(row, cells)| (row, self.new_row(cells, updated)))
+                    .collect();
+            }
+            TableEvent::Expire => {}
+        }
+        if let Some(deadline) = self.deadline(updated) {
+            self.rows.retain(|_, record| record.updated >= deadline);
+        }
+    }
+}
+
+impl TableState {
+    /// Rows updated before the deadline are expired.
+    fn deadline(&self, now: Timestamp) -> Option<Timestamp> {
+        let ttl_ms = self.spec.ttl? as i64 * 1_000;
+        Some(Timestamp(now.0 - ttl_ms))
+    }
+
+    /// Checks that some rows will be expired on the next update.
+    pub fn has_expired(&self, now: Timestamp) -> bool {
+        self.deadline(now)
+            .map(|deadline| self.rows.values().any(|record| record.updated < deadline))
+            .unwrap_or_default()
+    }
+
+    fn cell_mut(&mut self, row: Row, col: Col, updated: Timestamp) -> Option<&mut Cell> {
+        if self.spec.columns.contains_key(&col) {
+            let record = self.rows.get_mut(&row)?;
+            record.updated = updated;
+            Some(record.cols.entry(col).or_default())
+        } else {
+            None
         }
     }
+
+    fn new_row(&self, cells: Vec<(Col, String)>, updated: Timestamp) -> RowRecord {
+        let mut record = RowRecord::new(updated);
+        record.cols = cells
+            .into_iter()
+            .filter(|(col, _)| self.spec.columns.contains_key(col))
+            .map(|(col, value)| {
+                let cell = Cell {
+                    value,
+                    ..Cell::default()
+                };
+                (col, cell)
+            })
+            .collect();
+        record
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TableEvent {
-    AddRow { row: Row },
-    DelRow { row: Row },
-    SetCell { row: Row, col: Col, value: String },
+    AddRow {
+        row: Row,
+    },
+    DelRow {
+        row: Row,
+    },
+    SetCell {
+        row: Row,
+        col: Col,
+        value: String,
+    },
+    SetStyle {
+        row: Row,
+        col: Col,
+        style: CellStyle,
+    },
+    /// Replaces all cells of the row or creates it.
+    SetRow {
+        row: Row,
+        cells: Vec<(Col, String)>,
+    },
+    /// Sets provided cells only. The row is created if not exists.
+    UpsertRow {
+        row: Row,
+        cells: Vec<(Col, String)>,
+    },
+    /// Replaces all rows of the table.
+    SetRows {
+        rows: Vec<(Row, Vec<(Col, String)>)>,
+    },
+    /// Removes rows expired by the time of the event.
+    Expire,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// How values of a column are formatted and compared.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ColKind {
+    Text,
+    Number,
+    /// Milliseconds since the Unix epoch.
+    Timestamp,
+    /// A ratio where `1.0` is `100%`.
+    Percentage,
+}
+
+impl Default for ColKind {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ColRecord {
     pub title: String,
+    pub kind: ColKind,
+    /// The caption and the divisor of numbers.
+    pub label: Label,
+}
+
+impl ColRecord {
+    pub fn new(title: impl Into<String>, kind: ColKind) -> Self {
+        Self {
+            title: title.into(),
+            kind,
+            label: Label::default(),
+        }
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.label = label;
+        self
+    }
+}
+
+impl From<&str> for ColRecord {
+    fn from(title: &str) -> Self {
+        Self::new(title, ColKind::Text)
+    }
+}
+
+impl From<String> for ColRecord {
+    fn from(title: String) -> Self {
+        Self::new(title, ColKind::Text)
+    }
+}
+
+/// A hint how to highlight a cell.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CellStyle {
+    Normal,
+    Positive,
+    Negative,
+    Muted,
+}
+
+impl Default for CellStyle {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Cell {
+    pub value: String,
+    pub style: CellStyle,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RowRecord {
     #[serde(with = "vectorize")]
-    pub cols: BTreeMap<Col, String>,
+    pub cols: BTreeMap<Col, Cell>,
+    /// The time of the last update.
+    pub updated: Timestamp,
+}
+
+impl RowRecord {
+    fn new(updated: Timestamp) -> Self {
+        Self {
+            cols: BTreeMap::new(),
+            updated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(state: &mut TableState, ts: i64, event: TableEvent) {
+        let event = TimedEvent {
+            timestamp: Timestamp(ts),
+            event,
+        };
+        state.apply(event);
+    }
+
+    fn cells(values: &[&str]) -> Vec<(Col, String)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| (Col(idx as u64), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rows_ttl() {
+        let spec = TableSpec {
+            columns: vec![(Col(0), "Name".into())].into_iter().collect(),
+            sort: None,
+            ttl: Some(1),
+        };
+        let mut state = TableState::from(spec);
+        let row = Row(1);
+        apply(
+            &mut state,
+            0,
+            TableEvent::UpsertRow {
+                row,
+                cells: cells(&["a", "b"]),
+            },
+        );
+        apply(&mut state, 500, TableEvent::AddRow { row: Row(2) });
+        assert_eq!(state.rows[&row].cols.len(), 1);
+        assert!(!state.has_expired(Timestamp(1_000)));
+        assert!(state.has_expired(Timestamp(1_001)));
+        apply(
+            &mut state,
+            1_200,
+            TableEvent::UpsertRow {
+                row: Row(2),
+                cells: cells(&["c"]),
+            },
+        );
+        assert_eq!(state.rows.len(), 1);
+        let rows = vec![(Row(3), cells(&["d"])), (Row(4), cells(&["e"]))];
+        apply(&mut state, 1_300, TableEvent::SetRows { rows });
+        assert_eq!(
+            state.rows.keys().copied().collect::<Vec<_>>(),
+            vec![Row(3), Row(4)]
+        );
+    }
+
+    #[test]
+    fn test_rows_expire() {
+        let spec = TableSpec {
+            columns: vec![(Col(0), "Name".into())].into_iter().collect(),
+            sort: None,
+            ttl: Some(1),
+        };
+        let mut state = TableState::from(spec);
+        apply(&mut state, 0, TableEvent::AddRow { row: Row(1) });
+        apply(&mut state, 500, TableEvent::AddRow { row: Row(2) });
+        // Only the ticker sends events after this point
+        apply(&mut state, 1_000, TableEvent::Expire);
+        assert_eq!(state.rows.len(), 2);
+        apply(&mut state, 1_200, TableEvent::Expire);
+        assert_eq!(state.rows.keys().copied().collect::<Vec<_>>(), vec![Row(2)]);
+        assert!(!state.has_expired(Timestamp(1_500)));
+        assert!(state.has_expired(Timestamp(1_501)));
+        apply(&mut state, 1_600, TableEvent::Expire);
+        assert!(state.rows.is_empty());
+    }
 }
@@ -2,11 +2,19 @@ use super::state::*;
 use derive_more::{Deref, DerefMut};
 use rill_derive::TracerOpts;
 use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
 use rrpack_basis::{AutoPath, BindedTracer};
+use std::time::Duration;
+
+/// How often rows are checked for the expiration.
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(TracerOpts, Clone, Default)]
 pub struct TableOpts {
-    pub columns: Vec<(u64, String)>,
+    pub columns: Vec<(u64, ColRecord)>,
+    pub sort_by: Option<u64>,
+    pub descending: Option<bool>,
+    pub ttl: Option<u32>,
 }
 
 impl From<TableOpts> for TableSpec {
@@ -14,12 +22,18 @@ impl From<TableOpts> for TableSpec {
         let columns = opts
             .columns
             .into_iter()
-            .map(|(col_id, title)| {
-                let record = ColRecord { title };
-                (Col(col_id), record)
-            })
+            .map(|(col_id, record)| (Col(col_id), record))
             .collect();
-        Self { columns }
+        let descending = opts.descending.unwrap_or_default();
+        let sort = opts.sort_by.map(|col_id| Sort {
+            col: Col(col_id),
+            descending,
+        });
+        Self {
+            columns,
+            sort,
+            ttl: opts.ttl,
+        }
     }
 }
 
@@ -30,20 +44,33 @@ pub struct Table {
 
 impl Table {
     pub fn new(auto_path: impl Into<AutoPath>, mode: FlowMode, spec: impl Into<TableSpec>) -> Self {
-        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        let spec = spec.into();
+        let expirable = spec.ttl.is_some();
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec);
+        if expirable {
+            // Rows have to be removed even if the table is not updated anymore
+            tracer.ticker(EXPIRE_INTERVAL, |state: &TableState| {
+                let event = timed(TableEvent::Expire)?;
+                if state.has_expired(event.timestamp) {
+                    Some(event)
+                } else {
+                    None
+                }
+            });
+        }
         Self { tracer }
     }
 
     /// Adds a new row
     pub fn add_row(&self, row: Row) {
         let event = TableEvent::AddRow { row };
-        self.tracer.send(event, None);
+        self.send_event(event);
     }
 
     /// Deletes a row by id
     pub fn del_row(&self, row: Row) {
         let event = TableEvent::DelRow { row };
-        self.tracer.send(event, None);
+        self.send_event(event);
     }
 
     /// Sets a value to the cell
@@ -53,6 +80,57 @@ impl Table {
             col,
             value: value.to_string(),
         };
-        self.tracer.send(event, None);
+        self.send_event(event);
+    }
+
+    /// Sets a highlighting style of the cell
+    pub fn set_style(&self, row: Row, col: Col, style: CellStyle) {
+        let event = TableEvent::SetStyle { row, col, style };
+        self.send_event(event);
+    }
+
+    /// Replaces all cells of the row or creates it
+    pub fn set_row<T: ToString>(&self, row: Row, cells: impl IntoIterator<Item = (Col, T)>) {
+        let event = TableEvent::SetRow {
+            row,
+            cells: into_cells(cells),
+        };
+        self.send_event(event);
     }
+
+    /// Sets provided cells of the row or creates it
+    pub fn upsert_row<T: ToString>(&self, row: Row, cells: impl IntoIterator<Item = (Col, T)>) {
+        let event = TableEvent::UpsertRow {
+            row,
+            cells: into_cells(cells),
+        };
+        self.send_event(event);
+    }
+
+    /// Replaces all rows of the table at once
+    pub fn set_rows<I, T>(&self, rows: impl IntoIterator<Item = (Row, I)>)
+    where
+        I: IntoIterator<Item = (Col, T)>,
+        T: ToString,
+    {
+        let rows = rows
+            .into_iter()
+            .map(|(row, cells)| (row, into_cells(cells)))
+            .collect();
+        let event = TableEvent::SetRows { rows };
+        self.send_event(event);
+    }
+
+    fn send_event(&self, event: TableEvent) {
+        if let Some(event) = timed(event) {
+            self.tracer.send(event, None);
+        }
+    }
+}
+
+fn into_cells<T: ToString>(cells: impl IntoIterator<Item = (Col, T)>) -> Vec<(Col, String)> {
+    cells
+        .into_iter()
+        .map(|(col, value)| (col, value.to_string()))
+        .collect()
 }
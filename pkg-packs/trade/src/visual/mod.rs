@@ -1 +1,17 @@
+//! Live components for trading.
 
+pub mod ohlc;
+#[cfg(feature = "engine")]
+pub use ohlc::{Ohlc, OhlcOpts};
+
+pub mod order_book;
+#[cfg(feature = "engine")]
+pub use order_book::{OrderBook, OrderBookOpts};
+
+pub mod positions;
+#[cfg(feature = "engine")]
+pub use positions::{Positions, PositionsOpts};
+
+pub mod tape;
+#[cfg(feature = "engine")]
+pub use tape::{Tape, TapeOpts};
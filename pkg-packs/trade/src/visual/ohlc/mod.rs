@@ -0,0 +1,9 @@
+//! Candlestick chart that aggregates trades by intervals.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
@@ -0,0 +1,220 @@
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::{StreamType, Timestamp};
+use rrpack_basis::frames::{TimedEvent, TimedFrame};
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcSpec {
+    /// The duration of a candle (in seconds).
+    pub interval: u32,
+    /// How long to keep candles (in seconds).
+    pub retain: u32,
+}
+
+impl Default for OhlcSpec {
+    fn default() -> Self {
+        Self {
+            interval: 60,
+            retain: 3_600,
+        }
+    }
+}
+
+impl OhlcSpec {
+    pub fn interval_ms(&self) -> i64 {
+        self.interval.max(1) as i64 * 1_000
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub price: f64,
+    pub volume: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    /// The time of the earliest trade.
+    pub opened_at: Timestamp,
+    /// The time of the latest trade.
+    pub closed_at: Timestamp,
+}
+
+impl From<&TimedEvent<Trade>> for Candle {
+    fn from(trade: &TimedEvent<Trade>) -> Self {
+        let price = trade.event.price;
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: trade.event.volume,
+            opened_at: trade.timestamp,
+            closed_at: trade.timestamp,
+        }
+    }
+}
+
+impl Candle {
+    /// Adds a trade. Trades may come out of order.
+    fn add(&mut self, trade: &TimedEvent<Trade>) {
+        let price = trade.event.price;
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        if trade.timestamp < self.opened_at {
+            self.open = price;
+            self.opened_at = trade.timestamp;
+        }
+        if trade.timestamp >= self.closed_at {
+            self.close = price;
+            self.closed_at = trade.timestamp;
+        }
+        self.volume += trade.event.volume;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcState {
+    pub spec: OhlcSpec,
+    /// Every candle has a timestamp of the beginning of its interval.
+    pub frame: TimedFrame<Candle>,
+}
+
+impl From<OhlcSpec> for OhlcState {
+    fn from(spec: OhlcSpec) -> Self {
+        let depth_ms = spec.retain as i64 * 1_000 + spec.interval_ms();
+        let frame = TimedFrame::new(depth_ms);
+        Self { spec, frame }
+    }
+}
+
+impl PackFlow for OhlcState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for OhlcState {
+    type Action = ();
+    type Event = OhlcEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            OhlcEvent::Trade(trade) => {
+                let ts = trade.timestamp.0;
+                let start = ts - ts.rem_euclid(self.spec.interval_ms());
+                // Late trades are added to their candles if they are still retained.
+                let candle = self
+                    .frame
+                    .iter_mut()
+                    .rev()
+                    .take_while(|candle| candle.timestamp.0 >= start)
+                    .find(|candle| candle.timestamp.0 == start);
+                if let Some(candle) = candle {
+                    candle.event.add(&trade);
+                    return;
+                }
+                let candle = TimedEvent {
+                    timestamp: Timestamp(start),
+                    event: Candle::from(&trade),
+                };
+                // A late trade in a gap gets its own candle
+                if !self.frame.insert_ordered(candle) {
+                    log::warn!("A trade at {} is older than retained candles.", ts);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OhlcEvent {
+    Trade(TimedEvent<Trade>),
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    fn trade(state: &mut OhlcState, ts: i64, price: f64, volume: f64) {
+        let event = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: Trade { price, volume },
+        };
+        state.apply(OhlcEvent::Trade(event));
+    }
+
+    #[test]
+    fn test_candles() {
+        let spec = OhlcSpec {
+            interval: 1,
+            retain: 10,
+        };
+        let mut state = OhlcState::from(spec);
+        trade(&mut state, 1_100, 10.0, 1.0);
+        trade(&mut state, 1_200, 12.0, 2.0);
+        trade(&mut state, 1_300, 9.0, 1.0);
+        trade(&mut state, 2_500, 11.0, 1.0);
+        trade(&mut state, 1_900, 10.5, 1.0);
+        assert_eq!(state.frame.len(), 2);
+        let first = &state.frame[0];
+        assert_eq!(first.timestamp, Timestamp(1_000));
+        assert_eq!(first.event.open, 10.0);
+        assert_eq!(first.event.high, 12.0);
+        assert_eq!(first.event.low, 9.0);
+        assert_eq!(first.event.close, 10.5);
+        assert_eq!(first.event.volume, 5.0);
+        assert_eq!(state.frame[1].event.open, 11.0);
+    }
+
+    #[test]
+    fn test_out_of_order_trades() {
+        let spec = OhlcSpec {
+            interval: 1,
+            retain: 10,
+        };
+        let mut state = OhlcState::from(spec);
+        trade(&mut state, 1_500, 10.0, 1.0);
+        trade(&mut state, 1_200, 8.0, 1.0);
+        trade(&mut state, 1_400, 9.0, 1.0);
+        trade(&mut state, 1_100, 7.0, 1.0);
+        let candle = &state.frame[0].event;
+        assert_eq!(candle.open, 7.0);
+        assert_eq!(candle.close, 10.0);
+        assert_eq!((candle.low, candle.high), (7.0, 10.0));
+        assert_eq!(candle.opened_at, Timestamp(1_100));
+        assert_eq!(candle.closed_at, Timestamp(1_500));
+        trade(&mut state, 1_500, 11.0, 1.0);
+        assert_eq!(state.frame[0].event.close, 11.0);
+    }
+
+    #[test]
+    fn test_late_candles() {
+        let spec = OhlcSpec {
+            interval: 1,
+            retain: 10,
+        };
+        let mut state = OhlcState::from(spec);
+        trade(&mut state, 1_100, 10.0, 1.0);
+        trade(&mut state, 5_100, 11.0, 1.0);
+        // Fills the gap between candles
+        trade(&mut state, 3_500, 12.0, 2.0);
+        let starts: Vec<_> = state.frame.iter().map(|c| c.timestamp.0).collect();
+        assert_eq!(starts, vec![1_000, 3_000, 5_000]);
+        assert_eq!(state.frame[1].event.volume, 2.0);
+        // Older than retained candles
+        trade(&mut state, -20_000, 1.0, 1.0);
+        assert_eq!(state.frame.len(), 3);
+    }
+}
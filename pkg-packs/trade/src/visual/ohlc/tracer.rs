@@ -0,0 +1,46 @@
+use super::state::*;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct OhlcOpts {
+    pub interval: Option<u32>,
+    pub retain: Option<u32>,
+}
+
+impl From<OhlcOpts> for OhlcSpec {
+    fn from(opts: OhlcOpts) -> Self {
+        let default = OhlcSpec::default();
+        Self {
+            interval: opts.interval.unwrap_or(default.interval),
+            retain: opts.retain.unwrap_or(default.retain),
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Ohlc {
+    tracer: BindedTracer<OhlcState>,
+}
+
+impl Ohlc {
+    pub fn new(auto_path: impl Into<AutoPath>, mode: FlowMode, spec: impl Into<OhlcSpec>) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    /// Adds a trade to the current candle.
+    pub fn trade(&self, price: impl Into<f64>, volume: impl Into<f64>) {
+        let trade = Trade {
+            price: price.into(),
+            volume: volume.into(),
+        };
+        if let Some(trade) = timed(trade) {
+            let msg = OhlcEvent::Trade(trade);
+            self.tracer.send(msg, None);
+        }
+    }
+}
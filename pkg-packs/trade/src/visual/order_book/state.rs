@@ -0,0 +1,226 @@
+use ordered_float::OrderedFloat;
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::StreamType;
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type Price = OrderedFloat<f64>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookSpec {
+    /// Max amount of price levels to keep for every side.
+    pub depth: u32,
+}
+
+impl Default for OrderBookSpec {
+    fn default() -> Self {
+        Self { depth: 20 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookState {
+    pub spec: OrderBookSpec,
+    /// Volumes of bid levels.
+    #[serde(with = "vectorize")]
+    pub bids: BTreeMap<Price, f64>,
+    /// Volumes of ask levels.
+    #[serde(with = "vectorize")]
+    pub asks: BTreeMap<Price, f64>,
+}
+
+impl From<OrderBookSpec> for OrderBookState {
+    fn from(spec: OrderBookSpec) -> Self {
+        Self {
+            spec,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+}
+
+impl OrderBookState {
+    /// Bid levels from the best (the highest) price.
+    pub fn bids(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(price, volume)| (price.into_inner(), *volume))
+    }
+
+    /// Ask levels from the best (the lowest) price.
+    pub fn asks(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.asks
+            .iter()
+            .map(|(price, volume)| (price.into_inner(), *volume))
+    }
+
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.keys().next_back().map(|price| price.into_inner())
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.keys().next().map(|price| price.into_inner())
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_ask()? + self.best_bid()?) / 2.0)
+    }
+
+    /// Levels with accumulated volumes from the best price.
+    pub fn cumulative(&self, side: Side) -> Vec<(f64, f64)> {
+        let mut total = 0.0;
+        let accumulate = |(price, volume): (f64, f64)| {
+            total += volume;
+            (price, total)
+        };
+        match side {
+            Side::Bid => self.bids().map(accumulate).collect(),
+            Side::Ask => self.asks().map(accumulate).collect(),
+        }
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut BTreeMap<Price, f64> {
+        match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        }
+    }
+
+    /// Removes levels that are the most distant from the spread.
+    fn trim(&mut self) {
+        let depth = self.spec.depth as usize;
+        while self.bids.len() > depth {
+            let worst = self.bids.keys().next().copied();
+            worst.and_then(|price| self.bids.remove(&price));
+        }
+        while self.asks.len() > depth {
+            let worst = self.asks.keys().next_back().copied();
+            worst.and_then(|price| self.asks.remove(&price));
+        }
+    }
+}
+
+/// Collects levels skipping empty ones like updates do.
+fn levels(pairs: Vec<(f64, f64)>) -> BTreeMap<Price, f64> {
+    pairs
+        .into_iter()
+        .filter(|(_, volume)| *volume > 0.0)
+        .map(|(price, volume)| (OrderedFloat(price), volume))
+        .collect()
+}
+
+impl PackFlow for OrderBookState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for OrderBookState {
+    type Action = ();
+    type Event = OrderBookEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            OrderBookEvent::Upsert {
+                side,
+                price,
+                volume,
+            } => {
+                let levels = self.side_mut(side);
+                if volume > 0.0 {
+                    levels.insert(OrderedFloat(price), volume);
+                } else {
+                    levels.remove(&OrderedFloat(price));
+                }
+            }
+            OrderBookEvent::Delete { side, price } => {
+                self.side_mut(side).remove(&OrderedFloat(price));
+            }
+            OrderBookEvent::Snapshot { bids, asks } => {
+                self.bids = levels(bids);
+                self.asks = levels(asks);
+            }
+        }
+        self.trim();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrderBookEvent {
+    /// Sets the volume of a level. The level is removed if the volume is zero.
+    Upsert {
+        side: Side,
+        price: f64,
+        volume: f64,
+    },
+    Delete {
+        side: Side,
+        price: f64,
+    },
+    /// Replaces all levels with `(price, volume)` pairs.
+    /// Levels with zero volumes are skipped.
+    Snapshot {
+        bids: Vec<(f64, f64)>,
+        asks: Vec<(f64, f64)>,
+    },
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    fn upsert(state: &mut OrderBookState, side: Side, price: f64, volume: f64) {
+        let event = OrderBookEvent::Upsert {
+            side,
+            price,
+            volume,
+        };
+        state.apply(event);
+    }
+
+    #[test]
+    fn test_order_book() {
+        let mut state = OrderBookState::from(OrderBookSpec { depth: 2 });
+        assert_eq!(state.spread(), None);
+        upsert(&mut state, Side::Bid, 99.0, 1.0);
+        upsert(&mut state, Side::Bid, 98.0, 2.0);
+        upsert(&mut state, Side::Bid, 97.0, 3.0);
+        upsert(&mut state, Side::Ask, 101.0, 1.0);
+        upsert(&mut state, Side::Ask, 102.0, 2.0);
+        upsert(&mut state, Side::Ask, 103.0, 3.0);
+        assert_eq!(state.bids.len(), 2);
+        assert_eq!(state.best_ask(), Some(101.0));
+        assert_eq!(state.spread(), Some(2.0));
+        assert_eq!(state.mid(), Some(100.0));
+        assert_eq!(
+            state.cumulative(Side::Ask),
+            vec![(101.0, 1.0), (102.0, 3.0)]
+        );
+        upsert(&mut state, Side::Bid, 99.0, 0.0);
+        assert_eq!(state.best_bid(), Some(98.0));
+        state.apply(OrderBookEvent::Snapshot {
+            bids: vec![(10.0, 1.0), (11.0, 0.0)],
+            asks: vec![(12.0, 0.0)],
+        });
+        assert_eq!(state.best_bid(), Some(10.0));
+        assert!(state.asks.is_empty());
+        assert_eq!(state.mid(), None);
+    }
+}
@@ -0,0 +1,65 @@
+use super::state::*;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct OrderBookOpts {
+    pub depth: Option<u32>,
+}
+
+impl From<OrderBookOpts> for OrderBookSpec {
+    fn from(opts: OrderBookOpts) -> Self {
+        Self {
+            depth: opts.depth.unwrap_or_else(|| OrderBookSpec::default().depth),
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct OrderBook {
+    tracer: BindedTracer<OrderBookState>,
+}
+
+impl OrderBook {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<OrderBookSpec>,
+    ) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    /// Sets the volume of a level. Zero volume removes the level.
+    pub fn upsert(&self, side: Side, price: impl Into<f64>, volume: impl Into<f64>) {
+        let msg = OrderBookEvent::Upsert {
+            side,
+            price: price.into(),
+            volume: volume.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    pub fn delete(&self, side: Side, price: impl Into<f64>) {
+        let msg = OrderBookEvent::Delete {
+            side,
+            price: price.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    /// Replaces all levels with `(price, volume)` pairs.
+    pub fn snapshot(
+        &self,
+        bids: impl IntoIterator<Item = (f64, f64)>,
+        asks: impl IntoIterator<Item = (f64, f64)>,
+    ) {
+        let msg = OrderBookEvent::Snapshot {
+            bids: bids.into_iter().collect(),
+            asks: asks.into_iter().collect(),
+        };
+        self.tracer.send(msg, None);
+    }
+}
@@ -0,0 +1,9 @@
+//! Positions of instruments with average prices and PnL.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
@@ -0,0 +1,150 @@
+use crate::visual::tape::Direction;
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::StreamType;
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionsSpec {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Position {
+    /// Positive for long and negative for short positions.
+    pub quantity: f64,
+    /// Average price of the open quantity.
+    pub avg_price: f64,
+    pub realized: f64,
+    /// The last mark price.
+    pub mark: Option<f64>,
+}
+
+impl Position {
+    pub fn unrealized(&self) -> f64 {
+        self.mark
+            .map(|mark| (mark - self.avg_price) * self.quantity)
+            .unwrap_or_default()
+    }
+
+    fn fill(&mut self, direction: Direction, price: f64, quantity: f64) {
+        let delta = match direction {
+            Direction::Buy => quantity,
+            Direction::Sell => -quantity,
+        };
+        let held = self.quantity;
+        if held == 0.0 || held.signum() == delta.signum() {
+            let total = held.abs() + delta.abs();
+            if total > 0.0 {
+                self.avg_price = (self.avg_price * held.abs() + price * delta.abs()) / total;
+            }
+        } else {
+            let closed = delta.abs().min(held.abs());
+            self.realized += (price - self.avg_price) * closed * held.signum();
+            if delta.abs() > held.abs() {
+                // The position is reversed
+                self.avg_price = price;
+            }
+        }
+        self.quantity = held + delta;
+        if self.quantity == 0.0 {
+            self.avg_price = 0.0;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionsState {
+    pub spec: PositionsSpec,
+    pub positions: BTreeMap<String, Position>,
+}
+
+impl From<PositionsSpec> for PositionsState {
+    fn from(spec: PositionsSpec) -> Self {
+        Self {
+            spec,
+            positions: BTreeMap::new(),
+        }
+    }
+}
+
+impl PositionsState {
+    pub fn realized(&self) -> f64 {
+        self.positions.values().map(|pos| pos.realized).sum()
+    }
+
+    pub fn unrealized(&self) -> f64 {
+        self.positions.values().map(Position::unrealized).sum()
+    }
+}
+
+impl PackFlow for PositionsState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for PositionsState {
+    type Action = ();
+    type Event = PositionsEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            PositionsEvent::Fill {
+                instrument,
+                direction,
+                price,
+                quantity,
+            } => {
+                let position = self.positions.entry(instrument).or_default();
+                position.fill(direction, price, quantity);
+            }
+            PositionsEvent::Mark { instrument, price } => {
+                let position = self.positions.entry(instrument).or_default();
+                position.mark = Some(price);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PositionsEvent {
+    Fill {
+        instrument: String,
+        direction: Direction,
+        price: f64,
+        quantity: f64,
+    },
+    Mark {
+        instrument: String,
+        price: f64,
+    },
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_pnl() {
+        let mut pos = Position::default();
+        pos.fill(Direction::Buy, 10.0, 1.0);
+        pos.fill(Direction::Buy, 20.0, 1.0);
+        assert_eq!(pos.quantity, 2.0);
+        assert_eq!(pos.avg_price, 15.0);
+        pos.mark = Some(17.0);
+        assert_eq!(pos.unrealized(), 4.0);
+        pos.fill(Direction::Sell, 25.0, 1.0);
+        assert_eq!(pos.realized, 10.0);
+        assert_eq!(pos.avg_price, 15.0);
+        pos.fill(Direction::Sell, 5.0, 3.0);
+        assert_eq!(pos.realized, 0.0);
+        assert_eq!(pos.quantity, -2.0);
+        assert_eq!(pos.avg_price, 5.0);
+        assert_eq!(pos.unrealized(), -24.0);
+    }
+}
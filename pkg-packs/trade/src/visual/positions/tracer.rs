@@ -0,0 +1,57 @@
+use super::state::*;
+use crate::visual::tape::Direction;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct PositionsOpts {}
+
+impl From<PositionsOpts> for PositionsSpec {
+    fn from(_opts: PositionsOpts) -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Positions {
+    tracer: BindedTracer<PositionsState>,
+}
+
+impl Positions {
+    pub fn new(
+        auto_path: impl Into<AutoPath>,
+        mode: FlowMode,
+        spec: impl Into<PositionsSpec>,
+    ) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    /// Applies an executed order to the position of the instrument.
+    pub fn fill(
+        &self,
+        instrument: impl ToString,
+        direction: Direction,
+        price: impl Into<f64>,
+        quantity: impl Into<f64>,
+    ) {
+        let msg = PositionsEvent::Fill {
+            instrument: instrument.to_string(),
+            direction,
+            price: price.into(),
+            quantity: quantity.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+
+    /// Sets the mark price to calculate the unrealized PnL.
+    pub fn mark(&self, instrument: impl ToString, price: impl Into<f64>) {
+        let msg = PositionsEvent::Mark {
+            instrument: instrument.to_string(),
+            price: price.into(),
+        };
+        self.tracer.send(msg, None);
+    }
+}
@@ -0,0 +1,9 @@
+//! Time and sales: the latest trades with their directions.
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "engine")]
+pub mod tracer;
+#[cfg(feature = "engine")]
+pub use tracer::*;
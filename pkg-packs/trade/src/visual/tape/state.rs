@@ -0,0 +1,107 @@
+use rill_protocol::flow::core::Flow;
+use rill_protocol::io::provider::StreamType;
+use rrpack_basis::frames::{Frame, TimedEvent};
+use rrpack_basis::manifest::description::{Layer, PackFlow};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapeSpec {
+    /// Max amount of trades to keep.
+    pub size: u32,
+}
+
+impl Default for TapeSpec {
+    fn default() -> Self {
+        Self { size: 50 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapeRecord {
+    pub direction: Direction,
+    pub price: f64,
+    pub volume: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapeState {
+    pub spec: TapeSpec,
+    pub frame: Frame<TimedEvent<TapeRecord>>,
+}
+
+impl From<TapeSpec> for TapeState {
+    fn from(spec: TapeSpec) -> Self {
+        let frame = Frame::new(spec.size);
+        Self { spec, frame }
+    }
+}
+
+impl PackFlow for TapeState {
+    fn layer() -> Layer {
+        Layer::Visual
+    }
+}
+
+impl Flow for TapeState {
+    type Action = ();
+    type Event = TapeEvent;
+
+    fn stream_type() -> StreamType {
+        StreamType::from(module_path!())
+    }
+
+    fn apply(&mut self, event: Self::Event) {
+        match event {
+            TapeEvent::Add(record) => {
+                self.frame.insert_pop(record);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TapeEvent {
+    Add(TimedEvent<TapeRecord>),
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)] // Consts are used only!
+mod tests {
+    use super::*;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn add(state: &mut TapeState, ts: i64, price: f64) {
+        let record = TapeRecord {
+            direction: Direction::Buy,
+            price,
+            volume: 1.0,
+        };
+        let event = TimedEvent {
+            timestamp: Timestamp(ts),
+            event: record,
+        };
+        state.apply(TapeEvent::Add(event));
+    }
+
+    #[test]
+    fn test_tape_limit() {
+        let mut state = TapeState::from(TapeSpec { size: 3 });
+        add(&mut state, 1, 10.0);
+        add(&mut state, 2, 11.0);
+        assert_eq!(state.frame.len(), 2);
+        add(&mut state, 3, 12.0);
+        add(&mut state, 4, 13.0);
+        add(&mut state, 5, 14.0);
+        assert_eq!(state.frame.len(), 3);
+        // The oldest trades are removed
+        let prices: Vec<_> = state.frame.iter().map(|r| r.event.price).collect();
+        assert_eq!(prices, vec![12.0, 13.0, 14.0]);
+        assert_eq!(state.frame.back().unwrap().timestamp, Timestamp(5));
+    }
+}
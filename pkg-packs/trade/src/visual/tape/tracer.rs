@@ -0,0 +1,43 @@
+use super::state::*;
+use derive_more::{Deref, DerefMut};
+use rill_derive::TracerOpts;
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_basis::{AutoPath, BindedTracer};
+
+#[derive(TracerOpts, Clone, Default)]
+pub struct TapeOpts {
+    pub size: Option<u32>,
+}
+
+impl From<TapeOpts> for TapeSpec {
+    fn from(opts: TapeOpts) -> Self {
+        Self {
+            size: opts.size.unwrap_or_else(|| TapeSpec::default().size),
+        }
+    }
+}
+
+#[derive(Debug, Deref, DerefMut, Clone)]
+pub struct Tape {
+    tracer: BindedTracer<TapeState>,
+}
+
+impl Tape {
+    pub fn new(auto_path: impl Into<AutoPath>, mode: FlowMode, spec: impl Into<TapeSpec>) -> Self {
+        let tracer = BindedTracer::new(auto_path.into(), mode, spec.into());
+        Self { tracer }
+    }
+
+    pub fn add(&self, direction: Direction, price: impl Into<f64>, volume: impl Into<f64>) {
+        let record = TapeRecord {
+            direction,
+            price: price.into(),
+            volume: volume.into(),
+        };
+        if let Some(record) = timed(record) {
+            let msg = TapeEvent::Add(record);
+            self.tracer.send(msg, None);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod client_assistant;
+pub mod notifier;
 pub mod supervisor;
 
 mod error;
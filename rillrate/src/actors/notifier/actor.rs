@@ -0,0 +1,232 @@
+mod delivery;
+
+use crate::actors::supervisor::NodeSupervisor;
+use anyhow::Error;
+use async_trait::async_trait;
+use delivery::Delivery;
+use meio::{Actor, Consumer, Context, IdOf, InterruptedBy, StartedBy, TaskEliminated, TaskError};
+use rate_config::config::notifier::NotifierConfig;
+use rill_protocol::io::provider::Path;
+use rrpack_prime::transparent::alert::notification::{self, Notification};
+use rrpack_prime::transparent::alert::{RecordKind, Severity};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use strum::{EnumIter, IntoEnumIterator};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// The window of the rate limit.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Sends alert notifications to sinks from the config.
+pub struct Notifier {
+    client: reqwest::Client,
+    sinks: Vec<Sink>,
+    /// Severities of firing rules to resolve them with the same severity.
+    firing: HashMap<(Path, String), Severity>,
+}
+
+impl Notifier {
+    pub fn new(notifiers: Vec<NotifierConfig>) -> Self {
+        let sinks = notifiers
+            .into_iter()
+            .map(|config| Sink {
+                config: Arc::new(config),
+                sent: VecDeque::new(),
+            })
+            .collect();
+        Self {
+            client: reqwest::Client::new(),
+            sinks,
+            firing: HashMap::new(),
+        }
+    }
+}
+
+struct Sink {
+    config: Arc<NotifierConfig>,
+    sent: VecDeque<Instant>,
+}
+
+impl Sink {
+    fn accepts(&mut self, notification: &Notification, now: Instant) -> bool {
+        let delivery = self.config.delivery();
+        let min_severity = delivery.min_severity.unwrap_or_default();
+        if notification.severity < min_severity {
+            return false;
+        }
+        if let Some(limit) = delivery.rate_limit {
+            while let Some(ts) = self.sent.front() {
+                if now.duration_since(*ts) > RATE_WINDOW {
+                    self.sent.pop_front();
+                } else {
+                    break;
+                }
+            }
+            if self.sent.len() >= limit as usize {
+                log::warn!(
+                    "Rate limit of the notifier reached. Skipped: {}",
+                    notification.text
+                );
+                return false;
+            }
+            self.sent.push_back(now);
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum Group {
+    Notifications,
+    Deliveries,
+}
+
+impl Actor for Notifier {
+    type GroupBy = Group;
+}
+
+#[async_trait]
+impl StartedBy<NodeSupervisor> for Notifier {
+    async fn handle(&mut self, ctx: &mut Context<Self>) -> Result<(), Error> {
+        ctx.termination_sequence(Group::iter().collect());
+        if !self.sinks.is_empty() {
+            log::info!("Alert notifiers activated: {}", self.sinks.len());
+            let rx = UnboundedReceiverStream::new(notification::subscribe());
+            ctx.attach(rx, (), Group::Notifications);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InterruptedBy<NodeSupervisor> for Notifier {
+    async fn handle(&mut self, ctx: &mut Context<Self>) -> Result<(), Error> {
+        ctx.shutdown();
+        Ok(())
+    }
+}
+
+impl Notifier {
+    fn track(&mut self, notification: &mut Notification) {
+        if let Some(rule) = notification.rule.clone() {
+            let key = (notification.path.clone(), rule);
+            match notification.kind {
+                RecordKind::Notify => {}
+                RecordKind::Firing => {
+                    self.firing.insert(key, notification.severity);
+                }
+                RecordKind::Resolved => {
+                    if let Some(severity) = self.firing.remove(&key) {
+                        notification.severity = severity;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Consumer<Notification> for Notifier {
+    async fn handle(
+        &mut self,
+        mut notification: Notification,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), Error> {
+        self.track(&mut notification);
+        let now = Instant::now();
+        for sink in &mut self.sinks {
+            if sink.accepts(&notification, now) {
+                let task = Delivery::new(
+                    self.client.clone(),
+                    sink.config.clone(),
+                    notification.clone(),
+                );
+                ctx.spawn_task(task, (), Group::Deliveries);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskEliminated<Delivery, ()> for Notifier {
+    async fn handle(
+        &mut self,
+        _id: IdOf<Delivery>,
+        _tag: (),
+        result: Result<(), TaskError>,
+        _ctx: &mut Context<Self>,
+    ) -> Result<(), Error> {
+        if let Err(err) = result {
+            log::error!("Alert notification not delivered: {}", err);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate_config::config::notifier::DeliveryConfig;
+    use rill_protocol::io::provider::Timestamp;
+
+    fn sink(min_severity: Option<Severity>, rate_limit: Option<u32>) -> Sink {
+        let delivery = DeliveryConfig {
+            min_severity,
+            rate_limit,
+            retries: None,
+        };
+        let config = NotifierConfig::Command {
+            program: "true".into(),
+            args: Vec::new(),
+            delivery,
+        };
+        Sink {
+            config: Arc::new(config),
+            sent: VecDeque::new(),
+        }
+    }
+
+    fn notification(severity: Severity) -> Notification {
+        Notification {
+            path: Path::single("alert"),
+            timestamp: Timestamp(0),
+            kind: RecordKind::Notify,
+            rule: None,
+            severity,
+            text: "text".into(),
+        }
+    }
+
+    #[test]
+    fn test_accepts_severity() {
+        let now = Instant::now();
+        let mut all = sink(None, None);
+        assert!(all.accepts(&notification(Severity::Info), now));
+        assert!(all.accepts(&notification(Severity::Critical), now));
+        let mut warn = sink(Some(Severity::Warn), None);
+        assert!(!warn.accepts(&notification(Severity::Info), now));
+        assert!(warn.accepts(&notification(Severity::Warn), now));
+        assert!(warn.accepts(&notification(Severity::Critical), now));
+        let mut critical = sink(Some(Severity::Critical), None);
+        assert!(!critical.accepts(&notification(Severity::Warn), now));
+        assert!(critical.accepts(&notification(Severity::Critical), now));
+    }
+
+    #[test]
+    fn test_accepts_rate_limit() {
+        let now = Instant::now();
+        let mut sink = sink(Some(Severity::Warn), Some(2));
+        let warn = notification(Severity::Warn);
+        assert!(sink.accepts(&warn, now));
+        // Skipped notifications don't consume the limit
+        assert!(!sink.accepts(&notification(Severity::Info), now));
+        assert!(sink.accepts(&warn, now + Duration::from_secs(30)));
+        assert!(!sink.accepts(&warn, now + Duration::from_secs(59)));
+        // The first notification left the window
+        assert!(sink.accepts(&warn, now + Duration::from_secs(61)));
+        assert!(!sink.accepts(&warn, now + Duration::from_secs(62)));
+        assert!(sink.accepts(&warn, now + Duration::from_secs(91)));
+    }
+}
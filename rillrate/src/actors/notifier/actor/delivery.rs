@@ -0,0 +1,283 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use meio::LiteTask;
+use rate_config::config::notifier::NotifierConfig;
+use reqwest::header::CONTENT_TYPE;
+use rrpack_prime::transparent::alert::{Notification, RecordKind, Severity};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::sleep;
+
+const DEFAULT_RETRIES: u32 = 3;
+
+/// The delay before the first retry. It's doubled for every next attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Fields of a notification available for templates and commands.
+#[derive(Debug, Serialize)]
+struct Payload {
+    path: String,
+    timestamp: i64,
+    kind: RecordKind,
+    rule: Option<String>,
+    severity: Severity,
+    text: String,
+}
+
+impl From<Notification> for Payload {
+    fn from(notification: Notification) -> Self {
+        Self {
+            path: notification.path.to_string(),
+            timestamp: notification.timestamp.0,
+            kind: notification.kind,
+            rule: notification.rule,
+            severity: notification.severity,
+            text: notification.text,
+        }
+    }
+}
+
+impl Payload {
+    /// Pairs of field names and their plain values.
+    fn fields(&self) -> Result<Vec<(String, String)>, Error> {
+        let fields = match serde_json::to_value(self)? {
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(value) => value,
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    };
+                    (key, value)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Ok(fields)
+    }
+
+    /// Replaces `{{field}}` placeholders with JSON-escaped values.
+    fn render(&self, template: &str) -> Result<String, Error> {
+        let mut body = template.to_string();
+        for (key, value) in self.fields()? {
+            let escaped = serde_json::to_string(&value)?;
+            let escaped = &escaped[1..escaped.len() - 1];
+            body = body.replace(&format!("{{{{{}}}}}", key), escaped);
+        }
+        Ok(body)
+    }
+}
+
+pub struct Delivery {
+    client: reqwest::Client,
+    config: Arc<NotifierConfig>,
+    payload: Payload,
+}
+
+impl Delivery {
+    pub fn new(
+        client: reqwest::Client,
+        config: Arc<NotifierConfig>,
+        notification: Notification,
+    ) -> Self {
+        Self {
+            client,
+            config,
+            payload: notification.into(),
+        }
+    }
+
+    async fn deliver(&self) -> Result<(), Error> {
+        match self.config.as_ref() {
+            NotifierConfig::Webhook { url, body, .. } => {
+                let body = match body {
+                    Some(template) => self.payload.render(template)?,
+                    None => serde_json::to_string(&self.payload)?,
+                };
+                self.client
+                    .post(url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+            NotifierConfig::Command { program, args, .. } => {
+                let envs = self
+                    .payload
+                    .fields()?
+                    .into_iter()
+                    .map(|(key, value)| (format!("RR_ALERT_{}", key.to_uppercase()), value));
+                let status = Command::new(program)
+                    .args(args)
+                    .envs(envs)
+                    .kill_on_drop(true)
+                    .status()
+                    .await?;
+                if !status.success() {
+                    return Err(Error::msg(format!("{} failed: {}", program, status)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LiteTask for Delivery {
+    type Output = ();
+
+    async fn interruptable_routine(mut self) -> Result<Self::Output, Error> {
+        let retries = self.config.delivery().retries.unwrap_or(DEFAULT_RETRIES);
+        let mut delay = RETRY_DELAY;
+        let mut attempt = 0;
+        loop {
+            match self.deliver().await {
+                Ok(()) => {
+                    return Ok(());
+                }
+                Err(err) if attempt < retries => {
+                    attempt += 1;
+                    log::warn!("Alert delivery failed (attempt {}): {}", attempt, err);
+                    sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(err) => {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate_config::config::notifier::DeliveryConfig;
+    use rill_protocol::io::provider::Timestamp;
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+
+    fn notification(rule: Option<&str>) -> Notification {
+        Notification {
+            path: "app.alert".parse().unwrap(),
+            timestamp: Timestamp(1_000),
+            kind: RecordKind::Firing,
+            rule: rule.map(String::from),
+            severity: Severity::Critical,
+            text: "CPU is \"high\"\n".into(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let payload = Payload::from(notification(Some("cpu above 90")));
+        let template = r#"{"text": "{{text}}", "severity": "{{severity}}", "kind": "{{kind}}", "ts": {{timestamp}}, "rule": "{{rule}}", "path": "{{path}}", "other": "{{other}}"}"#;
+        let body = payload.render(template).unwrap();
+        let value: Value = serde_json::from_str(&body).unwrap();
+        let expected = json!({
+            "text": "CPU is \"high\"\n",
+            "severity": "critical",
+            "kind": "firing",
+            "ts": 1_000,
+            "rule": "cpu above 90",
+            "path": "app.alert",
+            "other": "{{other}}",
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_render_empty_fields() {
+        let payload = Payload::from(notification(None));
+        let body = payload.render("[{{rule}}] {{text}}").unwrap();
+        assert_eq!(body, r#"[] CPU is \"high\"\n"#);
+        assert_eq!(payload.render("").unwrap(), "");
+    }
+
+    fn webhook(url: String, retries: u32) -> Arc<NotifierConfig> {
+        let delivery = DeliveryConfig {
+            min_severity: None,
+            rate_limit: None,
+            retries: Some(retries),
+        };
+        let config = NotifierConfig::Webhook {
+            url,
+            body: None,
+            delivery,
+        };
+        Arc::new(config)
+    }
+
+    /// Responds to requests with the statuses and returns received bodies.
+    async fn serve(statuses: Vec<u16>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                bodies.push(read_body(&mut stream).await);
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    async fn read_body(stream: &mut TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "the request is incomplete");
+            data.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&data);
+            if let Some(idx) = text.find("\r\n\r\n") {
+                let length = text[..idx]
+                    .lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                    .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                    .unwrap_or_default();
+                let body = &data[idx + 4..];
+                if body.len() >= length {
+                    return String::from_utf8_lossy(&body[..length]).into_owned();
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retry() {
+        let (url, server) = serve(vec![500, 200]).await;
+        let client = reqwest::Client::new();
+        let delivery = Delivery::new(client, webhook(url, 1), notification(None));
+        delivery.interruptable_routine().await.unwrap();
+        let bodies = server.await.unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0], bodies[1]);
+        let value: Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(value["severity"], "critical");
+        assert_eq!(value["path"], "app.alert");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_exhausted() {
+        let (url, server) = serve(vec![500, 503]).await;
+        let client = reqwest::Client::new();
+        let delivery = Delivery::new(client, webhook(url, 1), notification(None));
+        assert!(delivery.interruptable_routine().await.is_err());
+        assert_eq!(server.await.unwrap().len(), 2);
+    }
+}
@@ -1,11 +1,13 @@
 mod assets;
 mod config;
 mod node;
+mod notifier;
 
 use crate::actors::client_assistant::NodeClientAssistant;
 use anyhow::Error;
 use async_trait::async_trait;
 use meio::{Actor, Context, InteractionHandler, InterruptedBy, StartedBy, System};
+use rate_config::config::notifier::NotifierConfig;
 use rate_core::actors::client_session::SessionAcl;
 use rate_core::actors::node::{NodeConfig, NodeLink};
 use rate_core::actors::supervisor::{link, Supervisor};
@@ -14,14 +16,16 @@ use strum::{EnumIter, IntoEnumIterator};
 
 pub struct NodeSupervisor {
     config: NodeConfig,
+    notifiers: Vec<NotifierConfig>,
     global_acl: SessionAcl,
     node: Option<NodeLink<Self>>,
 }
 
 impl NodeSupervisor {
-    pub fn new(config: NodeConfig) -> Self {
+    pub fn new(config: NodeConfig, notifiers: Vec<NotifierConfig>) -> Self {
         Self {
             config,
+            notifiers,
             global_acl: SessionAcl::new(),
             node: None,
         }
@@ -35,6 +39,7 @@ impl Supervisor for NodeSupervisor {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum Group {
     ConfigWatcher,
+    Notifier,
     Assets,
     Node,
 }
@@ -49,6 +54,7 @@ impl StartedBy<System> for NodeSupervisor {
         ctx.termination_sequence(Group::iter().collect());
 
         self.spawn_config_watcher(ctx);
+        self.spawn_notifier(ctx);
 
         self.spawn_node(ctx);
         self.node
@@ -0,0 +1,19 @@
+use super::{Group, NodeSupervisor};
+use crate::actors::notifier::Notifier;
+use anyhow::Error;
+use async_trait::async_trait;
+use meio::{Context, Eliminated, IdOf};
+
+impl NodeSupervisor {
+    pub(super) fn spawn_notifier(&mut self, ctx: &mut Context<Self>) {
+        let notifier = Notifier::new(self.notifiers.clone());
+        ctx.spawn_actor(notifier, Group::Notifier);
+    }
+}
+
+#[async_trait]
+impl Eliminated<Notifier> for NodeSupervisor {
+    async fn handle(&mut self, _id: IdOf<Notifier>, _ctx: &mut Context<Self>) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+//! The APM pack: system metrics of the process and the host.
+
+pub use rrpack_apm::system::*;
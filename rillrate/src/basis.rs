@@ -2,3 +2,4 @@
 
 pub use rrpack_basis::manifest::layouts::builder::*;
 pub use rrpack_basis::manifest::layouts::components::*;
+pub use rrpack_basis::manifest::layouts::layout::Size;
@@ -3,8 +3,10 @@
 #![warn(missing_docs)]
 
 mod actors;
+pub mod apm;
 pub mod basis;
 pub mod prime;
+pub mod trade;
 
 pub mod meio_addon;
 
@@ -14,6 +16,8 @@ use actors::supervisor::NodeSupervisor;
 use anyhow::Error;
 use meio::thread;
 use once_cell::sync::Lazy;
+use rate_config::config::server::RillRateConfig;
+use rill_config::ReadableConfig;
 use std::sync::Mutex;
 
 /// Contains core types
@@ -21,6 +25,8 @@ pub mod core {
     pub use rill_protocol::flow::core::FlowMode;
 }
 
+const CONFIG_PATH: &str = "rillrate.toml";
+
 static GLOBAL: Lazy<Mutex<Option<RillRate>>> = Lazy::new(|| Mutex::new(None));
 
 /// Tracks a lifetime of the `RillRate` engine.
@@ -31,21 +37,28 @@ pub struct RillRate {
 impl RillRate {
     /// Starts the engine.
     pub fn start(_name: impl ToString) -> Result<Self, Error> {
-        let actor = NodeSupervisor::new(Default::default());
+        let config = Self::configure();
+        let notifiers = config.notifier.unwrap_or_default();
+        let actor = NodeSupervisor::new(Default::default(), notifiers);
         let rt = thread::spawn(actor)?;
         Ok(RillRate { _rt: rt })
     }
 
-    /*
-    // TODO: Move to `ConfigWatcher` actor
-    async fn configure(&mut self) -> Result<(), Error> {
-        use crate::config::RillRateConfig;
-        use rate_config::ReadableConfig;
-
-        let config = RillRateConfig::read("rillrate.toml".into()).await?;
-        Ok(())
+    /// Reads the config file once. The default config is used if
+    /// the file doesn't exist or can't be parsed.
+    fn configure() -> RillRateConfig {
+        let data = match std::fs::read(CONFIG_PATH) {
+            Ok(data) => data,
+            Err(err) => {
+                log::debug!("Config {} not loaded: {}", CONFIG_PATH, err);
+                return RillRateConfig::default();
+            }
+        };
+        RillRateConfig::parse(&data).unwrap_or_else(|err| {
+            log::error!("Can't parse the config {}: {}", CONFIG_PATH, err);
+            RillRateConfig::default()
+        })
     }
-    */
 
     /// Pin the engine globally. Not needed to keep the handle in the scope.
     pub fn pin(self) -> Result<(), Error> {
@@ -1,5 +1,8 @@
 //! Re-exports `meio-addon`
 
+mod tracker;
+
 pub use rill_engine::tracers::meio::handler::FlowHandler;
 // TODO: Move this reexport somewhere?
 pub use rill_protocol::flow::core::Activity;
+pub use tracker::ActorTracker;
@@ -0,0 +1,275 @@
+use meio::{Actor, Address, Context};
+use rill_protocol::flow::core::FlowMode;
+use rrpack_basis::frames::timed;
+use rrpack_prime::visual::table::{Col, ColKind, ColRecord, Row, TableEvent};
+use rrpack_prime::visual::{Counter, CounterOpts, Gauge, GaugeOpts, Table, TableOpts};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const COL_ID: u64 = 0;
+const COL_ACTOR: u64 = 1;
+const COL_GROUP: u64 = 2;
+const COL_PARENT: u64 = 3;
+const COL_PENDING: u64 = 4;
+const COL_STARTED: u64 = 5;
+
+#[derive(Debug)]
+struct Record {
+    row: Row,
+    pending: i64,
+}
+
+/// Actors with their rows. Changes of the table are returned
+/// by the registry to keep rows consistent with registered actors.
+#[derive(Debug, Default)]
+struct Registry {
+    next_row: u64,
+    actors: HashMap<String, Record>,
+}
+
+impl Registry {
+    /// Adds a new row of the actor. The row of an actor
+    /// registered with the same id is deleted.
+    fn insert(&mut self, id: String, cells: Vec<(Col, String)>) -> Vec<TableEvent> {
+        let row = Row(self.next_row);
+        self.next_row += 1;
+        let mut events = Vec::new();
+        if let Some(prev) = self.actors.insert(id, Record { row, pending: 0 }) {
+            events.push(TableEvent::DelRow { row: prev.row });
+        }
+        events.push(TableEvent::SetRow { row, cells });
+        events
+    }
+
+    /// Deletes the row of the actor.
+    fn remove(&mut self, id: &str) -> Option<TableEvent> {
+        let record = self.actors.remove(id)?;
+        Some(TableEvent::DelRow { row: record.row })
+    }
+
+    /// Updates the amount of pending messages of the actor.
+    fn add_pending(&mut self, id: &str, delta: i64) -> Option<TableEvent> {
+        let record = self.actors.get_mut(id)?;
+        record.pending = (record.pending + delta).max(0);
+        Some(TableEvent::SetCell {
+            row: record.row,
+            col: COL_PENDING.into(),
+            value: record.pending.to_string(),
+        })
+    }
+
+    fn alive(&self) -> usize {
+        self.actors.len()
+    }
+}
+
+fn columns() -> Vec<(u64, ColRecord)> {
+    vec![
+        (COL_ID, ColRecord::from("Id")),
+        (COL_ACTOR, ColRecord::from("Actor")),
+        (COL_GROUP, ColRecord::from("Group")),
+        (COL_PARENT, ColRecord::from("Parent")),
+        (
+            COL_PENDING,
+            ColRecord::new("Pending (manual)", ColKind::Number),
+        ),
+        (COL_STARTED, ColRecord::new("Started", ColKind::Timestamp)),
+    ]
+}
+
+/// Reports the live tree of `meio` actors to the dashboard.
+///
+/// `meio` has no hooks for spawning and termination, so actors are
+/// registered explicitly by their parents:
+/// call `spawned` after `ctx.spawn_actor` and `terminated` in
+/// the `Eliminated` handler.
+///
+/// Queues of actors are not exposed by `meio` as well. The
+/// "Pending (manual)" column is not a mailbox size, it only counts
+/// messages marked with `enqueued` and not yet marked with `handled`.
+#[derive(Debug, Clone)]
+pub struct ActorTracker {
+    table: Table,
+    alive: Gauge,
+    spawned: Counter,
+    terminated: Counter,
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl ActorTracker {
+    /// Creates tracers in the dashboard with the `prefix` path
+    /// of the package and the dashboard (e.g. `app.meio`).
+    pub fn new(prefix: &str) -> Self {
+        let table = Table::new(
+            format!("{}.actors.tree", prefix),
+            FlowMode::Realtime,
+            TableOpts::default().columns(columns()).sort_by(COL_ID),
+        );
+        let alive = Gauge::new(
+            format!("{}.actors.alive", prefix),
+            FlowMode::Realtime,
+            GaugeOpts::default().min(0.0),
+        );
+        let spawned = Counter::new(
+            format!("{}.actors.spawned", prefix),
+            FlowMode::Realtime,
+            CounterOpts::default(),
+        );
+        let terminated = Counter::new(
+            format!("{}.actors.terminated", prefix),
+            FlowMode::Realtime,
+            CounterOpts::default(),
+        );
+        Self {
+            table,
+            alive,
+            spawned,
+            terminated,
+            registry: Arc::default(),
+        }
+    }
+
+    /// Registers a root actor that has no parent.
+    pub fn root<A: Actor>(&self, address: &Address<A>) {
+        self.register(address, None, None);
+    }
+
+    /// Registers a child actor spawned by the actor of the `ctx`.
+    pub fn spawned<P: Actor, A: Actor>(
+        &self,
+        ctx: &Context<P>,
+        address: &Address<A>,
+        group: impl Debug,
+    ) {
+        let parent = ctx.address().id().to_string();
+        self.register(address, Some(parent), Some(format!("{:?}", group)));
+    }
+
+    /// Removes the actor with the `id` from the tree.
+    pub fn terminated(&self, id: impl ToString) {
+        let mut registry = self.lock();
+        if let Some(event) = registry.remove(&id.to_string()) {
+            self.send(event);
+            self.alive.set(registry.alive() as f64);
+            self.terminated.inc(1);
+        }
+    }
+
+    /// Marks a message sent to the actor.
+    pub fn enqueued<A: Actor>(&self, address: &Address<A>) {
+        self.add_pending(address.id().to_string(), 1);
+    }
+
+    /// Marks a message handled by the actor of the `ctx`.
+    pub fn handled<A: Actor>(&self, ctx: &Context<A>) {
+        self.add_pending(ctx.address().id().to_string(), -1);
+    }
+
+    fn register<A: Actor>(
+        &self,
+        address: &Address<A>,
+        parent: Option<String>,
+        group: Option<String>,
+    ) {
+        let id = address.id().to_string();
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+        let cells = vec![
+            (COL_ID, id.clone()),
+            (COL_ACTOR, std::any::type_name::<A>().to_string()),
+            (COL_GROUP, group.unwrap_or_default()),
+            (COL_PARENT, parent.unwrap_or_default()),
+            (COL_PENDING, "0".to_string()),
+            (COL_STARTED, started.to_string()),
+        ];
+        let cells = cells
+            .into_iter()
+            .map(|(col, value)| (col.into(), value))
+            .collect();
+        let mut registry = self.lock();
+        for event in registry.insert(id, cells) {
+            self.send(event);
+        }
+        self.alive.set(registry.alive() as f64);
+        self.spawned.inc(1);
+    }
+
+    fn add_pending(&self, id: String, delta: i64) {
+        let mut registry = self.lock();
+        if let Some(event) = registry.add_pending(&id, delta) {
+            self.send(event);
+        }
+    }
+
+    fn send(&self, event: TableEvent) {
+        if let Some(event) = timed(event) {
+            self.table.send(event, None);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Registry> {
+        // The registry is always consistent, a poisoned lock can be reused.
+        self.registry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rill_protocol::flow::core::Flow;
+    use rrpack_prime::visual::table::{TableSpec, TableState};
+
+    fn apply(state: &mut TableState, events: impl IntoIterator<Item = TableEvent>) {
+        for event in events {
+            state.apply(timed(event).unwrap());
+        }
+    }
+
+    fn cells(id: &str) -> Vec<(Col, String)> {
+        vec![(COL_ID.into(), id.to_string())]
+    }
+
+    fn ids(state: &TableState) -> Vec<&str> {
+        state
+            .rows
+            .values()
+            .map(|record| record.cols[&COL_ID.into()].value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_registry() {
+        let opts = TableOpts::default().columns(columns());
+        let mut state = TableState::from(TableSpec::from(opts));
+        let mut registry = Registry::default();
+        apply(&mut state, registry.insert("1".into(), cells("1")));
+        apply(&mut state, registry.insert("2".into(), cells("2")));
+        assert_eq!(ids(&state), ["1", "2"]);
+        assert_eq!(registry.alive(), 2);
+        apply(&mut state, registry.add_pending("2", 1));
+        apply(&mut state, registry.add_pending("2", 1));
+        let pending =
+            |state: &TableState| state.rows[&Row(1)].cols[&COL_PENDING.into()].value.clone();
+        assert_eq!(pending(&state), "2");
+        apply(&mut state, registry.add_pending("2", -3));
+        assert_eq!(pending(&state), "0");
+        assert!(registry.add_pending("3", 1).is_none());
+        apply(&mut state, registry.remove("2"));
+        assert_eq!(ids(&state), ["1"]);
+        // Unknown and already terminated actors are ignored
+        assert!(registry.remove("2").is_none());
+        assert!(registry.remove("3").is_none());
+        assert_eq!(registry.alive(), 1);
+        // The row of an actor registered again is replaced
+        apply(&mut state, registry.insert("1".into(), cells("1")));
+        assert_eq!(ids(&state), ["1"]);
+        assert_eq!(state.rows.keys().copied().collect::<Vec<_>>(), [Row(2)]);
+        assert_eq!(registry.alive(), 1);
+    }
+}
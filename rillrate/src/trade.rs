@@ -0,0 +1,3 @@
+//! The trade pack: candlesticks, order books, positions, etc.
+
+pub use rrpack_trade::visual::*;